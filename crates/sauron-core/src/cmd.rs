@@ -0,0 +1,137 @@
+//! provides functionalities for commands to be executed by the system, such as
+//! when the application starts or after the application updates.
+//!
+use crate::{mt_dom::Callback, Dispatch};
+use std::{marker::PhantomData, rc::Rc};
+
+/// A command which is executed with the dispatcher of the program
+pub type Command<MSG> = Rc<dyn Fn(Rc<dyn Dispatch<MSG>>)>;
+
+/// Cmd is a command to be executed by the system.
+/// This is returned at the init function of a component and is executed right
+/// after instantiation of that component.
+/// Cmd required a dispatcher, which is the Program as an argument
+/// The emit function is called with the program argument.
+/// The callback is supplied with the dispatcher of the program and is then executed/emitted.
+///
+/// The APP type associates the Cmd with the Component that returns it.
+pub struct Cmd<APP, MSG> {
+    /// the functions to be executed with the dispatcher of the program
+    pub commands: Vec<Command<MSG>>,
+    _phantom_app: PhantomData<APP>,
+}
+
+impl<APP, MSG> Cmd<APP, MSG>
+where
+    MSG: 'static,
+{
+    /// creates a new Cmd from a function
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(Rc<dyn Dispatch<MSG>>) + 'static,
+    {
+        Cmd {
+            commands: vec![Rc::new(f)],
+            _phantom_app: PhantomData,
+        }
+    }
+
+    /// creates a unified Cmd which batches all the other Cmds in one.
+    pub fn batch(cmds: Vec<Self>) -> Self {
+        let mut commands = vec![];
+        for cmd in cmds {
+            commands.extend(cmd.commands);
+        }
+        Cmd {
+            commands,
+            _phantom_app: PhantomData,
+        }
+    }
+
+    /// A Cmd with no callback, similar to NoOp.
+    pub fn none() -> Self {
+        Cmd {
+            commands: vec![],
+            _phantom_app: PhantomData,
+        }
+    }
+
+    /// returns true if this Cmd has no callback
+    pub fn is_none(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Executes the Cmd
+    pub fn emit<DSP>(self, program: &DSP)
+    where
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let dispatcher: Rc<dyn Dispatch<MSG>> = Rc::new(program.clone());
+        for cb in self.commands {
+            cb(Rc::clone(&dispatcher));
+        }
+    }
+
+    /// map the Cmd returned from a sub component, such that it can be executed
+    /// by the Program of the parent component.
+    /// The MSG dispatched in the Cmd is wrapped into the parent MSG2 using `func`,
+    /// similar to `Node::map_msg`.
+    ///
+    /// Example:
+    /// ```rust,ignore
+    /// Msg::RowMsg(row_index, row_msg) => self.row_views[row_index]
+    ///     .update(row_msg)
+    ///     .map_msg(move |row_msg| Msg::RowMsg(row_index, row_msg)),
+    /// ```
+    pub fn map_msg<F, APP2, MSG2>(self, func: F) -> Cmd<APP2, MSG2>
+    where
+        F: Fn(MSG) -> MSG2 + 'static,
+        MSG2: 'static,
+    {
+        let mapper = Callback::from(func);
+        let commands = self
+            .commands
+            .into_iter()
+            .map(|cmd| {
+                let mapper = mapper.clone();
+                let mapped: Command<MSG2> =
+                    Rc::new(move |dispatcher: Rc<dyn Dispatch<MSG2>>| {
+                        cmd(Rc::new(MapDispatch {
+                            dispatcher,
+                            mapper: mapper.clone(),
+                        }))
+                    });
+                mapped
+            })
+            .collect();
+        Cmd {
+            commands,
+            _phantom_app: PhantomData,
+        }
+    }
+}
+
+/// A dispatcher which maps the MSG into MSG2 before dispatching it
+/// into the dispatcher of the parent program
+struct MapDispatch<MSG, MSG2> {
+    dispatcher: Rc<dyn Dispatch<MSG2>>,
+    mapper: Callback<MSG, MSG2>,
+}
+
+impl<MSG, MSG2> Dispatch<MSG> for MapDispatch<MSG, MSG2>
+where
+    MSG: 'static,
+    MSG2: 'static,
+{
+    fn dispatch(&self, msg: MSG) {
+        self.dispatcher.dispatch(self.mapper.emit(msg))
+    }
+
+    fn downgrade(&self) -> Option<Rc<dyn Dispatch<MSG>>> {
+        let dispatcher = self.dispatcher.downgrade()?;
+        Some(Rc::new(MapDispatch {
+            dispatcher,
+            mapper: self.mapper.clone(),
+        }))
+    }
+}
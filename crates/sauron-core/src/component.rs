@@ -1,4 +1,4 @@
-use crate::{Cmd, Node};
+use crate::{Cmd, Node, Sub};
 
 /// The app should implement this trait for it to be handled by the Program
 pub trait Component<MSG>
@@ -26,4 +26,12 @@ where
 
     /// Returns a node on how the component is presented.
     fn view(&self) -> Node<MSG>;
+
+    /// the event sources outside of the view which the component is listening to,
+    /// such as window events, intervals and animation frames.
+    /// This is re-evaluated after each update, so the subscriptions
+    /// can be turned on and off depending on the state of the component.
+    fn subscriptions(&self) -> Sub<MSG> {
+        Sub::none()
+    }
 }
@@ -0,0 +1,39 @@
+use std::rc::Rc;
+
+/// This trait is used in the DomUpdater to call the dispatch
+/// method when an event occured
+///
+/// The Program will implement Dispatch instead of sending it to the
+/// DomUpdater, this will simplify the amount of generics being defined.
+pub trait Dispatch<MSG> {
+    /// Executes the implementing dispatch function.
+    /// In this case the implementation is the Program
+    /// which is responsible for executing the update functions
+    /// using the msg supplied.
+    /// A new view will then be created and it will be diff to the previous view
+    /// which will produce patches.
+    /// These patched will then be applied to the browser DOM.
+    fn dispatch(&self, msg: MSG);
+
+    /// A dispatcher which doesn't keep the program alive, the msgs are ignored
+    /// once the program is dropped.
+    /// This is used by the listeners which are owned by the app, such as the WebSocket,
+    /// so they don't form a reference cycle with the program which owns the app.
+    /// Returns None when this dispatcher can not be weakly referenced.
+    fn downgrade(&self) -> Option<Rc<dyn Dispatch<MSG>>> {
+        None
+    }
+}
+
+/// The dispatcher which is passed into the `Cmd` is a type erased program,
+/// this allows it to be used in places where a concrete dispatcher is needed
+/// such as attaching event listeners.
+impl<MSG> Dispatch<MSG> for Rc<dyn Dispatch<MSG>> {
+    fn dispatch(&self, msg: MSG) {
+        self.as_ref().dispatch(msg)
+    }
+
+    fn downgrade(&self) -> Option<Rc<dyn Dispatch<MSG>>> {
+        self.as_ref().downgrade()
+    }
+}
@@ -3,30 +3,34 @@
 //!
 pub mod apply_patches;
 mod browser;
-pub mod cmd;
-mod component;
 mod created_node;
-mod dispatch;
 mod dom_updater;
-pub mod events;
-mod http;
+mod event_delegation;
+pub mod file_reader;
+pub mod http;
+mod hydrate;
 mod program;
+mod storage;
+mod sub_component;
+mod subscription;
+mod timer;
 mod util;
+mod websocket;
 mod window;
 
+pub use crate::html::events::{self, *};
 pub use browser::Browser;
-pub use component::Component;
 pub use created_node::CreatedNode;
-pub use dispatch::Dispatch;
 pub use dom_updater::DomUpdater;
-pub use events::*;
+pub use file_reader::FileReader;
 pub use http::Http;
 pub use program::Program;
+pub use storage::{Storage, StorageArea, StorageError};
+pub use sub_component::SubComponent;
+pub use timer::{Timer, TimerHandle};
 pub use util::{
     body, document, execute_in_request_animation_frame, history, now,
     performance, request_animation_frame, window,
 };
+pub use websocket::{Frame, Reconnect, SocketEvent, SocketHandle, WebSocket};
 pub use window::Window;
-
-/// alias Cmd to use Program as the APP
-pub type Cmd<APP, MSG> = cmd::Cmd<Program<APP, MSG>>;
@@ -3,6 +3,7 @@ use crate::{
     dom::{
         created_node,
         created_node::{ActiveClosure, CreatedNode},
+        sub_component::is_sub_component,
     },
     mt_dom::AttValue,
     Dispatch, Patch,
@@ -10,7 +11,7 @@ use crate::{
 use js_sys::Function;
 use std::collections::HashMap;
 use wasm_bindgen::{JsCast, JsValue};
-use web_sys::{Element, Node, Text};
+use web_sys::{Element, HtmlInputElement, HtmlOptionElement, Node, Text};
 
 /// Apply all of the patches to our old root node in order to create the new root node
 /// that we desire.
@@ -100,6 +101,8 @@ fn find_nodes_recursive(
     // We use child_nodes() instead of children() because children() ignores text nodes
     let children = node.child_nodes();
     let child_node_count = children.length();
+    let is_sub_component_node = node.node_type() == Node::ELEMENT_NODE
+        && is_sub_component(node.unchecked_ref());
 
     // If the root node matches, mark it for patching
     if let Some(tag) = nodes_to_find.get(&cur_node_idx) {
@@ -124,6 +127,11 @@ fn find_nodes_recursive(
 
     *cur_node_idx += 1;
 
+    // the content of a sub component is patched by its own program
+    if is_sub_component_node {
+        return (element_nodes_to_patch, text_nodes_to_patch);
+    }
+
     for i in 0..child_node_count {
         let child_node = children.item(i).expect("Expecting a child node");
 
@@ -180,6 +188,11 @@ fn get_node_descendant_data_vdom_id(root_element: &Element) -> Vec<u32> {
         data_vdom_id.push(vdom_id);
     }
 
+    // the closures of a sub component belongs to its own program
+    if is_sub_component(root_element) {
+        return data_vdom_id;
+    }
+
     let children = root_element.child_nodes();
     let child_node_count = children.length();
     for i in 0..child_node_count {
@@ -199,12 +212,20 @@ fn remove_event_listeners(
     node: &Element,
     old_closures: &mut ActiveClosure,
 ) -> Result<(), JsValue> {
+    // there is nothing to free, such as when the events are delegated
+    if old_closures.is_empty() {
+        return Ok(());
+    }
     let all_descendant_vdom_id = get_node_descendant_data_vdom_id(node);
     for vdom_id in all_descendant_vdom_id {
         if let Some(old_closure) = old_closures.get(&vdom_id) {
-            for (event, oc) in old_closure.iter() {
+            for (event, options, oc) in old_closure.iter() {
                 let func: &Function = oc.as_ref().unchecked_ref();
-                node.remove_event_listener_with_callback(event, func)?;
+                node.remove_event_listener_with_callback_and_bool(
+                    event,
+                    func,
+                    options.capture,
+                )?;
             }
 
             // remove closure active_closure in dom_updater to free up memory
@@ -227,17 +248,25 @@ fn remove_event_listener_with_name(
     node: &Element,
     old_closures: &mut ActiveClosure,
 ) -> Result<(), JsValue> {
+    // there is nothing to free, such as when the events are delegated
+    if old_closures.is_empty() {
+        return Ok(());
+    }
     let all_descendant_vdom_id = get_node_descendant_data_vdom_id(node);
     for vdom_id in all_descendant_vdom_id {
         if let Some(old_closure) = old_closures.get_mut(&vdom_id) {
-            for (event, oc) in old_closure.iter() {
+            for (event, options, oc) in old_closure.iter() {
                 if *event == event_name {
                     let func: &Function = oc.as_ref().unchecked_ref();
-                    node.remove_event_listener_with_callback(event, func)?;
+                    node.remove_event_listener_with_callback_and_bool(
+                        event,
+                        func,
+                        options.capture,
+                    )?;
                 }
             }
 
-            old_closure.retain(|(event, _oc)| *event != event_name);
+            old_closure.retain(|(event, _options, _oc)| *event != event_name);
 
             // remove closure active_closure in dom_updater to free up memory
             if old_closure.is_empty() {
@@ -309,6 +338,7 @@ where
                     match att_value {
                         AttValue::Plain(_) => {
                             node.remove_attribute(attr.name())?;
+                            reset_property(node, attr.name());
                         }
                         // it is an event listener
                         AttValue::Callback(_) => {
@@ -396,6 +426,24 @@ where
     }
 }
 
+/// reset the property which is set together with the attribute,
+/// since removing the attribute doesn't change the property once the user changed it
+fn reset_property(element: &Element, name: &str) {
+    match name {
+        "checked" => {
+            if let Some(input) = element.dyn_ref::<HtmlInputElement>() {
+                input.set_checked(false);
+            }
+        }
+        "selected" => {
+            if let Some(option) = element.dyn_ref::<HtmlOptionElement>() {
+                option.set_selected(false);
+            }
+        }
+        _ => (),
+    }
+}
+
 fn apply_text_patch<DSP, MSG>(
     program: Option<&DSP>,
     node: &Text,
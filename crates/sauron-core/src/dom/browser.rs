@@ -10,7 +10,9 @@ pub struct Browser;
 
 impl Browser {
     /// Creates a Cmd in which the MSG will be emitted
-    /// whenever the browser is resized
+    /// whenever the browser is resized.
+    /// The listener can not be removed, use `Sub::on_resize` in the
+    /// component subscriptions for a listener that can be turned off.
     pub fn on_resize<F, APP, MSG>(cb: F) -> Cmd<APP, MSG>
     where
         F: Fn(i32, i32) -> MSG + Clone + 'static,
@@ -26,14 +28,20 @@ impl Browser {
                     program.dispatch(msg);
                 }));
             crate::window()
-                .set_onresize(Some(resize_callback.as_ref().unchecked_ref()));
+                .add_event_listener_with_callback(
+                    "resize",
+                    resize_callback.as_ref().unchecked_ref(),
+                )
+                .expect("Unable to attach resize listener");
             resize_callback.forget();
         });
         cmd
     }
 
     /// attached a callback and will be triggered when the hash portion of the window location
-    /// url is changed
+    /// url is changed.
+    /// The listener can not be removed, use `Sub::on_hashchange` in the
+    /// component subscriptions for a listener that can be turned off.
     pub fn on_hashchange<F, APP, MSG>(cb: F) -> Cmd<APP, MSG>
     where
         F: Fn(String) -> MSG + Clone + 'static,
@@ -48,9 +56,12 @@ impl Browser {
                     let msg = cb_clone(hash);
                     program.dispatch(msg);
                 }));
-            crate::window().set_onhashchange(Some(
-                hashchange_callback.as_ref().unchecked_ref(),
-            ));
+            crate::window()
+                .add_event_listener_with_callback(
+                    "hashchange",
+                    hashchange_callback.as_ref().unchecked_ref(),
+                )
+                .expect("Unable to attach hashchange listener");
             hashchange_callback.forget();
         });
         cmd
@@ -1,15 +1,22 @@
 use crate::{
-    dom::Dispatch,
+    dom::sub_component::{mount_sub_component, DATA_SAURON_COMPONENT},
+    html::{
+        attributes::{
+            is_boolean_attr_enabled, merge_plain_values, EventFilter,
+            HTML_BOOLEAN_ATTRS,
+        },
+        events::{event_filters, event_options, EventOptions},
+    },
     mt_dom::{AttValue, Callback},
     prelude::AttributeValue,
-    Attribute, Event,
+    Attribute, Dispatch, Event,
 };
 use std::ops::Deref;
-use std::{collections::HashMap, fmt::Write, sync::Mutex};
+use std::{collections::HashMap, sync::Mutex};
 use wasm_bindgen::{closure::Closure, JsCast};
 use web_sys::{
-    self, Element, EventTarget, HtmlInputElement, HtmlTextAreaElement, Node,
-    Text,
+    self, AddEventListenerOptions, Element, EventTarget, HtmlInputElement,
+    HtmlOptionElement, HtmlSelectElement, HtmlTextAreaElement, Node, Text,
 };
 
 // Used to uniquely identify elements that contain closures so that the DomUpdater can
@@ -38,8 +45,16 @@ pub(crate) const DATA_SAURON_VDOM_ID: &str = "data-sauron-vdom-id";
 /// The u32 is a unique identifier that is associated with the DOM element that this closure is
 /// attached to.
 ///
-pub type ActiveClosure =
-    HashMap<u32, Vec<(&'static str, Closure<dyn FnMut(web_sys::Event)>)>>;
+/// The options of the listener are kept, since the `capture` option is needed
+/// to remove the listener.
+pub type ActiveClosure = HashMap<
+    u32,
+    Vec<(
+        &'static str,
+        EventOptions,
+        Closure<dyn FnMut(web_sys::Event)>,
+    )>,
+>;
 
 /// A node along with all of the closures that were created for that
 /// node's events and all of it's child node's events.
@@ -98,32 +113,12 @@ impl<T> CreatedNode<T> {
         }
     }
 
-    /// merge the plain values
-    fn merge_plain_attributes_values(
+    /// merge the plain values of the attribute with this name
+    pub(crate) fn merge_plain_attributes_values(
+        name: &str,
         attr_values: &[&AttributeValue],
     ) -> Option<String> {
-        let plain_values: Vec<String> = attr_values
-            .iter()
-            .flat_map(|att_value| match att_value {
-                AttributeValue::Simple(simple) => Some(simple.to_string()),
-                AttributeValue::Style(styles) => {
-                    let mut style_str = String::new();
-                    styles.iter().for_each(|s| {
-                        write!(style_str, "{};", s).expect("must write")
-                    });
-                    Some(style_str)
-                }
-                AttributeValue::FunctionCall(fvalue) => {
-                    Some(fvalue.to_string())
-                }
-                AttributeValue::Empty => None,
-            })
-            .collect();
-        if !plain_values.is_empty() {
-            Some(plain_values.join(" "))
-        } else {
-            None
-        }
+        merge_plain_values(name, attr_values)
     }
 
     /// returns (callbacks, plain_attribtues, function_calls)
@@ -183,7 +178,7 @@ impl<T> CreatedNode<T> {
 
         // set simple values
         if let Some(merged_plain_values) =
-            Self::merge_plain_attributes_values(&plain_values)
+            Self::merge_plain_attributes_values(attr.name(), &plain_values)
         {
             if let Some(ref namespace) = attr.namespace() {
                 // Warning NOTE: set_attribute_ns should only be called
@@ -208,23 +203,55 @@ impl<T> CreatedNode<T> {
                             element.dyn_ref::<HtmlTextAreaElement>()
                         {
                             textarea.set_value(&merged_plain_values);
+                        } else if let Some(select) =
+                            element.dyn_ref::<HtmlSelectElement>()
+                        {
+                            // this has no effect until the options are added,
+                            // so it is set again after the children are created
+                            select.set_value(&merged_plain_values);
+                        } else if let Some(option) =
+                            element.dyn_ref::<HtmlOptionElement>()
+                        {
+                            option.set_value(&merged_plain_values);
+                        } else {
+                            // li, button, meter, progress and param
+                            // reflect the value attribute
+                            element
+                                .set_attribute("value", &merged_plain_values)
+                                .expect("Set value attribute");
                         }
                     }
                     "checked" => {
                         if let Some(input) =
                             element.dyn_ref::<HtmlInputElement>()
                         {
-                            let checked = plain_values
-                                .first()
-                                .map(|av| {
-                                    av.get_simple()
-                                        .expect("must be a simple value")
-                                })
-                                .unwrap()
-                                .to_string();
-                            if !checked.is_empty() {
-                                input.set_checked(true);
-                            }
+                            input.set_checked(is_boolean_attr_enabled(
+                                &plain_values,
+                            ));
+                        }
+                    }
+                    // the attribute is only the default selection,
+                    // which is ignored once the user changed the selection
+                    "selected" => {
+                        if let Some(option) =
+                            element.dyn_ref::<HtmlOptionElement>()
+                        {
+                            option.set_selected(is_boolean_attr_enabled(
+                                &plain_values,
+                            ));
+                        }
+                    }
+                    // the boolean attributes are enabled by their presence,
+                    // so the disabled ones are removed instead of set to `false`
+                    name if HTML_BOOLEAN_ATTRS.contains(&name) => {
+                        if is_boolean_attr_enabled(&plain_values) {
+                            element
+                                .set_attribute(name, "")
+                                .expect("Set boolean attribute");
+                        } else {
+                            element
+                                .remove_attribute(name)
+                                .expect("Remove boolean attribute");
                         }
                     }
                     _ => {
@@ -238,7 +265,7 @@ impl<T> CreatedNode<T> {
 
         // do function calls such as set_inner_html
         if let Some(merged_func_values) =
-            Self::merge_plain_attributes_values(&func_values)
+            Self::merge_plain_attributes_values(attr.name(), &func_values)
         {
             match *attr.name() {
                 "inner_html" => element.set_inner_html(&merged_func_values),
@@ -246,38 +273,57 @@ impl<T> CreatedNode<T> {
             }
         }
 
-        // add callbacks using add_event_listener
-        for callback in callbacks {
-            let unique_id = create_unique_identifier();
+        // add callbacks using add_event_listener,
+        // without a program the listeners are not attached, such as when the events are delegated
+        if let Some(program) = program {
+            for callback in callbacks {
+                let unique_id = create_unique_identifier();
 
-            // set the data-sauron_vdom-id this will be read later on
-            // when it's time to remove this element and its closures and event listeners
-            element
-                .set_attribute(DATA_SAURON_VDOM_ID, &unique_id.to_string())
-                .expect("Could not set attribute on element");
-
-            closures.insert(unique_id, vec![]);
+                // set the data-sauron_vdom-id this will be read later on
+                // when it's time to remove this element and its closures and event listeners
+                element
+                    .set_attribute(DATA_SAURON_VDOM_ID, &unique_id.to_string())
+                    .expect("Could not set attribute on element");
 
-            if let Some(program) = program {
                 let event_str = attr.name();
+                let options = event_options(attr);
+                let filters = event_filters(attr);
                 let current_elm: &EventTarget =
                     element.dyn_ref().expect("unable to cast to event targe");
                 let closure_wrap: Closure<dyn FnMut(web_sys::Event)> =
-                    create_closure_wrap(program, &callback);
-                current_elm
-                    .add_event_listener_with_callback(
-                        event_str,
-                        closure_wrap.as_ref().unchecked_ref(),
-                    )
-                    .expect("Unable to attached event listener");
-                closures
-                    .get_mut(&unique_id)
-                    .expect("Unable to get closure")
-                    .push((event_str, closure_wrap));
+                    create_closure_wrap(program, &callback, &options, &filters);
+                add_event_listener(
+                    current_elm,
+                    event_str,
+                    &closure_wrap,
+                    &options,
+                );
+                closures.insert(
+                    unique_id,
+                    vec![(event_str, options, closure_wrap)],
+                );
             }
         }
     }
 
+    /// set the value of the select element, once its options are created
+    fn set_select_value<MSG>(
+        velem: &crate::Element<MSG>,
+        select: &HtmlSelectElement,
+    ) {
+        let values: Vec<&AttributeValue> = velem
+            .get_attributes()
+            .iter()
+            .filter(|attr| *attr.name() == "value")
+            .flat_map(|attr| attr.get_plain())
+            .collect();
+        if let Some(value) =
+            Self::merge_plain_attributes_values("value", &values)
+        {
+            select.set_value(&value);
+        }
+    }
+
     /// Build a DOM element by recursively creating DOM nodes for this element and it's
     /// children, it's children's children, etc.
     pub fn create_element_node<DSP, MSG>(
@@ -351,6 +397,16 @@ impl<T> CreatedNode<T> {
             }
         }
 
+        if let Some(select) = element.dyn_ref::<HtmlSelectElement>() {
+            Self::set_select_value(velem, select);
+        }
+
+        // the sub component is mounted right away, since it is already instantiated
+        if let Some(component_id) = element.get_attribute(DATA_SAURON_COMPONENT)
+        {
+            mount_sub_component(&component_id, &element);
+        }
+
         CreatedNode {
             node: element,
             closures,
@@ -359,9 +415,15 @@ impl<T> CreatedNode<T> {
 }
 
 /// This wrap into a closure the function that is dispatched when the event is triggered.
+/// The `prevent_default` and `stop_propagation` of the options are called on the event
+/// before the callback, so it works even for the events which are mapped,
+/// such as the `InputEvent`.
+/// Nothing is done for the events which are not accepted by the filters.
 pub(crate) fn create_closure_wrap<DSP, MSG>(
     program: &DSP,
     callback: &Callback<crate::Event, MSG>,
+    options: &EventOptions,
+    filters: &[EventFilter],
 ) -> Closure<dyn FnMut(web_sys::Event)>
 where
     MSG: 'static,
@@ -371,28 +433,50 @@ where
     // TODO: use a weak pointer here
     // let program_weak = Rc::downgrade(&program)
     let program_clone = program.clone();
+    let options = *options;
+    let filters = filters.to_vec();
 
     Closure::wrap(Box::new(move |event: web_sys::Event| {
-        // FIXME: need to allow users to control this
-        // Note:
-        // calling `event.stop_propagation()` to the containers of this element to have
-        // a more fine grain control and expected results,
-        // and for most cases this is what we want. We don't want the containing div of a button
-        // also receives that click event.
-        //event.stop_propagation();
-        // FIXME: need to allow users control this
-        //
+        if !filters.iter().all(|filter| filter.accepts(&event)) {
+            return;
+        }
         // Notes:
-        // - calling event.prevent_default() prevents the reloading the page in href links, which is what we
-        // want mostly in an SPA app
+        // - calling event.prevent_default() prevents the reloading the page in href links
         // - calling event.prevent_default() prevent InputEvent to trigger when KeyPressEvent is
         // also one of the event callback
-        // event.prevent_default();
+        // - the browser ignores prevent_default in passive listeners
+        if options.prevent_default && !options.passive {
+            event.prevent_default();
+        }
+        // the containers of this element will not receive the event
+        if options.stop_propagation {
+            event.stop_propagation();
+        }
         let msg = callback_clone.emit(event);
         program_clone.dispatch(msg);
     }))
 }
 
+/// attach the closure as the event listener of the target with the options
+pub(crate) fn add_event_listener(
+    target: &EventTarget,
+    event_name: &str,
+    closure: &Closure<dyn FnMut(web_sys::Event)>,
+    options: &EventOptions,
+) {
+    let listener_options = AddEventListenerOptions::new();
+    listener_options.set_passive(options.passive);
+    listener_options.set_capture(options.capture);
+    listener_options.set_once(options.once);
+    target
+        .add_event_listener_with_callback_and_add_event_listener_options(
+            event_name,
+            closure.as_ref().unchecked_ref(),
+            &listener_options,
+        )
+        .expect("Unable to attached event listener");
+}
+
 impl From<CreatedNode<Element>> for CreatedNode<Node> {
     fn from(other: CreatedNode<Element>) -> CreatedNode<Node> {
         CreatedNode {
@@ -3,16 +3,20 @@ use crate::{
     dom::{
         apply_patches::patch,
         created_node::{ActiveClosure, CreatedNode},
-        Dispatch,
+        event_delegation::{self, EventDelegation},
+        hydrate::hydrate_node,
     },
+    Dispatch,
 };
+use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
 use wasm_bindgen::JsCast;
 use web_sys::{self, Element, Node};
 
 /// Used for keeping a real DOM node up to date based on the current Node
 /// and a new incoming Node that represents our latest DOM state.
 pub struct DomUpdater<MSG> {
-    current_vdom: crate::Node<MSG>,
+    /// shared with the delegated event listeners, which look up the listeners in it
+    current_vdom: Rc<RefCell<crate::Node<MSG>>>,
     root_node: Node,
 
     /// The closures that are currently attached to elements in the page.
@@ -20,6 +24,9 @@ pub struct DomUpdater<MSG> {
     /// We keep these around so that they don't get dropped (and thus stop working);
     ///
     pub active_closures: ActiveClosure,
+
+    /// the listeners at the root node, when the events are delegated
+    event_delegation: Option<EventDelegation>,
 }
 
 impl<MSG> DomUpdater<MSG> {
@@ -29,12 +36,34 @@ impl<MSG> DomUpdater<MSG> {
         mount: &Node,
     ) -> DomUpdater<MSG> {
         DomUpdater {
-            current_vdom,
+            current_vdom: Rc::new(RefCell::new(current_vdom)),
             root_node: mount.clone(),
             active_closures: ActiveClosure::new(),
+            event_delegation: None,
         }
     }
 
+    /// Delegate the events to a single listener for each event type at the root node,
+    /// instead of attaching a closure to each of the elements.
+    /// This must be called before the current_vdom is mounted.
+    pub fn delegate_events(&mut self) {
+        self.event_delegation = Some(EventDelegation::new());
+    }
+
+    /// returns true if the events are delegated at the root node
+    pub fn is_delegating_events(&self) -> bool {
+        self.event_delegation.is_some()
+    }
+
+    /// count the event types listened at the root node,
+    /// this is always 0 when the events are not delegated
+    pub fn delegated_listener_len(&self) -> usize {
+        self.event_delegation
+            .as_ref()
+            .map(|event_delegation| event_delegation.len())
+            .unwrap_or(0)
+    }
+
     /// count the total active closures
     /// regardless of which element it attached to.
     pub fn active_closure_len(&self) -> usize {
@@ -64,7 +93,10 @@ where
         DSP: Dispatch<MSG> + Clone + 'static,
     {
         let created_node: CreatedNode<Node> =
-            CreatedNode::<Node>::create_dom_node(program, &self.current_vdom);
+            CreatedNode::<Node>::create_dom_node_opt(
+                self.listening_program(program),
+                &self.current_vdom.borrow(),
+            );
         if replace {
             let root_element: &Element = self.root_node.unchecked_ref();
             root_element
@@ -77,6 +109,47 @@ where
         }
         self.root_node = created_node.node;
         self.active_closures = created_node.closures;
+        self.listen_to_all_events(program);
+    }
+
+    /// the program which the listeners of the elements dispatch to,
+    /// None when the events are delegated so no closures are attached to the elements
+    fn listening_program<'a, DSP>(&self, program: &'a DSP) -> Option<&'a DSP> {
+        if self.is_delegating_events() {
+            None
+        } else {
+            Some(program)
+        }
+    }
+
+    /// listen at the root node to all the event types used in the current_vdom
+    fn listen_to_all_events<DSP>(&mut self, program: &DSP)
+    where
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let mut event_names = BTreeSet::new();
+        event_delegation::event_names(
+            &self.current_vdom.borrow(),
+            &mut event_names,
+        );
+        self.listen_to_events(program, event_names);
+    }
+
+    fn listen_to_events<DSP>(
+        &mut self,
+        program: &DSP,
+        event_names: BTreeSet<&'static str>,
+    ) where
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        if let Some(event_delegation) = self.event_delegation.as_mut() {
+            event_delegation.listen(
+                program,
+                &self.root_node,
+                &self.current_vdom,
+                event_names,
+            );
+        }
     }
 
     /// Mount the current_vdom replacing the actual browser DOM specified in the root_node
@@ -89,6 +162,33 @@ where
         self.mount(program, true);
     }
 
+    /// Hydrate the existing DOM in the root_node, such as the html rendered from the server,
+    /// with the current_vdom instead of recreating it.
+    /// The event listeners are attached to the existing elements and the parts
+    /// which does not match the current_vdom are fixed up.
+    pub fn hydrate<DSP>(&mut self, program: &DSP)
+    where
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let mut closures = ActiveClosure::new();
+        let current_vdom = self.current_vdom.borrow();
+        let root_path = match &*current_vdom {
+            crate::Node::Element(element) => element.tag().to_string(),
+            crate::Node::Text(_) => "text".to_string(),
+        };
+        let root_node = hydrate_node(
+            self.listening_program(program),
+            &current_vdom,
+            &self.root_node,
+            &mut closures,
+            &root_path,
+        );
+        drop(current_vdom);
+        self.root_node = root_node;
+        self.active_closures = closures;
+        self.listen_to_all_events(program);
+    }
+
     /// Create a new `DomUpdater`.
     ///
     /// A root `Node` will be created and appended (as a child) to your passed
@@ -131,19 +231,38 @@ where
     where
         DSP: Dispatch<MSG> + Clone + 'static,
     {
-        let patches = diff(&self.current_vdom, &new_vdom);
+        let current_vdom = self.current_vdom.borrow();
+        let patches = diff(&current_vdom, &new_vdom);
+        let event_names = match self.event_delegation.as_ref() {
+            // nothing is listened yet, such as after the msg is mapped
+            Some(delegation) if delegation.is_empty() => {
+                let mut event_names = BTreeSet::new();
+                event_delegation::event_names(&new_vdom, &mut event_names);
+                event_names
+            }
+            Some(delegation) => {
+                delegation.reset_fired(&current_vdom, &patches);
+                event_delegation::patch_event_names(&patches)
+            }
+            None => BTreeSet::new(),
+        };
         let active_closures = patch(
-            Some(program),
+            self.listening_program(program),
             self.root_node.clone(),
             &mut self.active_closures,
             patches,
         )
         .expect("Error in patching the dom");
+        drop(current_vdom);
         self.active_closures.extend(active_closures);
-        self.current_vdom = new_vdom;
+        *self.current_vdom.borrow_mut() = new_vdom;
+        self.listen_to_events(program, event_names);
     }
 
     /// map this DomUpdater such that the Node<MSG> will become Node<MSG2>
+    ///
+    /// When the events are delegated, the listeners at the root node are removed
+    /// since they dispatch the MSG, they are attached again in the next update with the program of MSG2.
     pub fn map_msg<F, MSG2>(self, func: F) -> DomUpdater<MSG2>
     where
         F: Fn(MSG) -> MSG2 + 'static,
@@ -153,11 +272,26 @@ where
             current_vdom,
             root_node,
             active_closures,
+            event_delegation,
         } = self;
+        let is_delegating_events = event_delegation.is_some();
+        // the vdom is shared with the listeners at the root node,
+        // which are removed and dropped together with the event delegation
+        drop(event_delegation);
+        let current_vdom = Rc::try_unwrap(current_vdom)
+            .unwrap_or_else(|_| {
+                unreachable!("the vdom is only shared with the root listeners")
+            })
+            .into_inner();
         DomUpdater {
-            current_vdom: current_vdom.map_msg(func),
+            current_vdom: Rc::new(RefCell::new(current_vdom.map_msg(func))),
             root_node,
             active_closures,
+            event_delegation: if is_delegating_events {
+                Some(EventDelegation::new())
+            } else {
+                None
+            },
         }
     }
 
@@ -0,0 +1,439 @@
+//! Event delegation attaches a single listener for each event type at the root element
+//! of the app, instead of a closure for each element which has an event listener.
+//!
+//! When an event is triggered, the DOM is walked from the event target up to the root
+//! to get the index of each node in its parent. The same indexes are then used to find
+//! the virtual node of the target and its ancestors in the current view,
+//! and their listeners are called in the same order the browser would have called them.
+//!
+//! Since there are no closures per element, the elements are not marked with
+//! `data-sauron-vdom-id` and nothing has to be freed when they are removed.
+//!
+//! The `once` listeners which are already called are kept with the path of their element,
+//! they are called again only when the element is replaced or its listener is added again.
+//!
+//! The differences to the listeners attached to the elements are:
+//! - the `current_target` of the event is the root element.
+//! - a `once` listener is called again after a sibling of its element,
+//!   or of one of its ancestors, is inserted or removed since the paths are shifted.
+use crate::{
+    dom::created_node::add_event_listener,
+    html::{
+        attributes::EventFilter,
+        events::{event_filters, event_options, EventOptions},
+    },
+    mt_dom::Callback,
+    Dispatch, Event, Patch,
+};
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap, HashSet},
+    rc::Rc,
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{EventTarget, Node};
+
+/// These events don't bubble, so they are listened to in the capture phase at the root
+const NON_BUBBLING_EVENTS: [&str; 20] = [
+    "focus",
+    "blur",
+    "mouseenter",
+    "mouseleave",
+    "pointerenter",
+    "pointerleave",
+    "scroll",
+    "load",
+    "error",
+    "abort",
+    "loadstart",
+    "progress",
+    "loadend",
+    "play",
+    "pause",
+    "ended",
+    "canplay",
+    "timeupdate",
+    "volumechange",
+    "toggle",
+];
+
+/// The listeners of an element which are called for the event,
+/// with their index in the listeners of the element
+type Listeners<MSG> =
+    Vec<(usize, Callback<Event, MSG>, EventOptions, Rc<[EventFilter]>)>;
+
+/// A `once` listener which is already called,
+/// identified with the path of its element, the event type and its index in the element
+type FiredListener = (Vec<usize>, String, usize);
+
+/// The listeners attached at the root element, keyed with the event type
+pub(crate) struct EventDelegation {
+    /// the root node which the listeners are attached to
+    root: Option<EventTarget>,
+    listeners: HashMap<&'static str, Closure<dyn FnMut(web_sys::Event)>>,
+    /// the `once` listeners which are already called
+    fired: Rc<RefCell<HashSet<FiredListener>>>,
+}
+
+impl EventDelegation {
+    pub(crate) fn new() -> Self {
+        EventDelegation {
+            root: None,
+            listeners: HashMap::new(),
+            fired: Rc::new(RefCell::new(HashSet::new())),
+        }
+    }
+
+    /// the number of event types listened at the root
+    pub(crate) fn len(&self) -> usize {
+        self.listeners.len()
+    }
+
+    /// returns true if no event type is listened at the root
+    pub(crate) fn is_empty(&self) -> bool {
+        self.listeners.is_empty()
+    }
+
+    /// listen to the event types at the root,
+    /// which are not listened to yet
+    pub(crate) fn listen<DSP, MSG>(
+        &mut self,
+        program: &DSP,
+        root_node: &Node,
+        vdom: &Rc<RefCell<crate::Node<MSG>>>,
+        event_names: BTreeSet<&'static str>,
+    ) where
+        MSG: 'static,
+        DSP: Clone + Dispatch<MSG> + 'static,
+    {
+        for event_name in event_names {
+            if self.listeners.contains_key(event_name) {
+                continue;
+            }
+            let program = program.clone();
+            let root = root_node.clone();
+            let vdom = Rc::clone(vdom);
+            let fired = Rc::clone(&self.fired);
+            let closure: Closure<dyn FnMut(web_sys::Event)> =
+                Closure::wrap(Box::new(move |event: web_sys::Event| {
+                    dispatch_event(&program, &root, &vdom, &fired, event);
+                }));
+            let options = EventOptions {
+                capture: NON_BUBBLING_EVENTS.contains(&event_name),
+                ..Default::default()
+            };
+            let target: &EventTarget = root_node.unchecked_ref();
+            add_event_listener(target, event_name, &closure, &options);
+            self.root = Some(target.clone());
+            self.listeners.insert(event_name, closure);
+        }
+    }
+
+    /// the `once` listeners of the elements changed by the patches can be called again,
+    /// since the elements are replaced, their listeners are added again
+    /// or the paths of their children are shifted
+    pub(crate) fn reset_fired<MSG>(
+        &self,
+        old_vdom: &crate::Node<MSG>,
+        patches: &[Patch<MSG>],
+    ) where
+        MSG: 'static,
+    {
+        let mut fired = self.fired.borrow_mut();
+        if fired.is_empty() {
+            return;
+        }
+        let node_idxs = patches.iter().map(|patch| patch.node_idx()).collect();
+        let paths = node_paths(old_vdom, &node_idxs);
+        for patch in patches {
+            let path = match paths.get(&patch.node_idx()) {
+                Some(path) => path,
+                None => continue,
+            };
+            match patch {
+                Patch::Replace(..)
+                | Patch::InsertChildren(..)
+                | Patch::RemoveChildren(..) => {
+                    fired.retain(|(fired_path, _, _)| {
+                        !fired_path.starts_with(path)
+                    });
+                }
+                Patch::AddAttributes(_tag, _node_idx, attrs) => {
+                    fired.retain(|(fired_path, event_name, _)| {
+                        fired_path != path
+                            || !attrs
+                                .iter()
+                                .any(|attr| attr.name() == event_name)
+                    });
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// the listeners are removed from the root,
+/// so the dropped closures are no longer called by the browser
+impl Drop for EventDelegation {
+    fn drop(&mut self) {
+        let root = match self.root.as_ref() {
+            Some(root) => root,
+            None => return,
+        };
+        for (event_name, closure) in self.listeners.iter() {
+            root.remove_event_listener_with_callback_and_bool(
+                event_name,
+                closure.as_ref().unchecked_ref(),
+                NON_BUBBLING_EVENTS.contains(event_name),
+            )
+            .expect("Unable to remove event listener");
+        }
+    }
+}
+
+/// the event types which have listeners in this node and its descendants
+pub(crate) fn event_names<MSG>(
+    node: &crate::Node<MSG>,
+    names: &mut BTreeSet<&'static str>,
+) where
+    MSG: 'static,
+{
+    if let crate::Node::Element(element) = node {
+        for attr in element.get_attributes() {
+            if !attr.get_callback().is_empty() {
+                names.insert(*attr.name());
+            }
+        }
+        for child in element.get_children() {
+            event_names(child, names);
+        }
+    }
+}
+
+/// the event types which have listeners in the nodes and attributes added by the patches
+pub(crate) fn patch_event_names<MSG>(
+    patches: &[Patch<MSG>],
+) -> BTreeSet<&'static str>
+where
+    MSG: 'static,
+{
+    let mut names = BTreeSet::new();
+    for patch in patches {
+        match patch {
+            Patch::InsertChildren(_, _, _, nodes)
+            | Patch::AppendChildren(_, _, nodes) => {
+                for node in nodes {
+                    event_names(node, &mut names);
+                }
+            }
+            Patch::Replace(_tag, _node_idx, node) => {
+                event_names(node, &mut names)
+            }
+            Patch::AddAttributes(_tag, _node_idx, attrs) => {
+                for attr in attrs {
+                    if !attr.get_callback().is_empty() {
+                        names.insert(*attr.name());
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    names
+}
+
+/// call the listeners of the event target and its ancestors in the current view
+fn dispatch_event<DSP, MSG>(
+    program: &DSP,
+    root_node: &Node,
+    vdom: &RefCell<crate::Node<MSG>>,
+    fired: &RefCell<HashSet<FiredListener>>,
+    event: web_sys::Event,
+) where
+    MSG: 'static,
+    DSP: Dispatch<MSG>,
+{
+    let target: Option<Node> =
+        event.target().and_then(|target| target.dyn_into().ok());
+    let path = match target.and_then(|target| dom_path(root_node, &target)) {
+        Some(path) => path,
+        None => return,
+    };
+    // the borrow is released before dispatching, since dispatching may update the view
+    let listeners = match vdom.try_borrow() {
+        Ok(vdom) => {
+            find_listeners(&vdom, &path, &event.type_(), event.bubbles())
+        }
+        Err(_) => {
+            log::warn!(
+                "{} event is ignored, it is triggered while the view is updated",
+                event.type_()
+            );
+            return;
+        }
+    };
+    let event_name = event.type_();
+    for (element_path, element_listeners) in listeners {
+        for (index, callback, options, filters) in element_listeners {
+            if !filters.iter().all(|filter| filter.accepts(&event)) {
+                continue;
+            }
+            if options.once {
+                let listener =
+                    (element_path.to_vec(), event_name.clone(), index);
+                if !fired.borrow_mut().insert(listener) {
+                    continue;
+                }
+            }
+            if options.prevent_default && !options.passive {
+                event.prevent_default();
+            }
+            if options.stop_propagation {
+                event.stop_propagation();
+            }
+            let msg = callback.emit(event.clone());
+            program.dispatch(msg);
+        }
+        // the ancestors will not receive the event,
+        // the listeners of the same element are still called
+        if event.cancel_bubble() {
+            break;
+        }
+    }
+}
+
+/// the index of each node from the root down to the target,
+/// None if the target is not inside the root
+fn dom_path(root_node: &Node, target: &Node) -> Option<Vec<usize>> {
+    let mut path = vec![];
+    let mut current = target.clone();
+    while !current.is_same_node(Some(root_node)) {
+        let parent = current.parent_node()?;
+        path.push(child_index(&current));
+        current = parent;
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// the index of the node in the children of the virtual node,
+/// the comment separators between the text nodes are not counted
+fn child_index(node: &Node) -> usize {
+    let mut index = 0;
+    let mut sibling = node.previous_sibling();
+    while let Some(current) = sibling {
+        if current.node_type() != Node::COMMENT_NODE {
+            index += 1;
+        }
+        sibling = current.previous_sibling();
+    }
+    index
+}
+
+/// the listeners of the virtual nodes along the path, grouped by element together with its path,
+/// in the order they are called: the capturing listeners from the root down to the target,
+/// then the other listeners from the target up to the root.
+/// Only the target listeners are called for the events which don't bubble.
+fn find_listeners<'p, MSG>(
+    vdom: &crate::Node<MSG>,
+    path: &'p [usize],
+    event_name: &str,
+    bubbles: bool,
+) -> Vec<(&'p [usize], Listeners<MSG>)>
+where
+    MSG: 'static,
+{
+    let mut chain = vec![vdom];
+    for index in path {
+        let child = chain
+            .last()
+            .and_then(|node| node.get_children())
+            .and_then(|children| children.get(*index));
+        match child {
+            Some(child) => chain.push(child),
+            // the target is inside a sub component
+            None => break,
+        }
+    }
+    let is_target_found = chain.len() == path.len() + 1;
+
+    let listeners: Vec<(Listeners<MSG>, Listeners<MSG>)> = chain
+        .iter()
+        .map(|node| element_listeners(node, event_name))
+        .collect();
+    let mut ordered = vec![];
+    for (i, (capturing, _)) in listeners.iter().enumerate() {
+        ordered.push((&path[..i], capturing.clone()));
+    }
+    for (i, (_, bubbling)) in listeners.iter().enumerate().rev() {
+        let is_target = is_target_found && i == listeners.len() - 1;
+        if bubbles || is_target {
+            ordered.push((&path[..i], bubbling.clone()));
+        }
+    }
+    ordered.retain(|(_, element_listeners)| !element_listeners.is_empty());
+    ordered
+}
+
+/// the capturing and the other listeners of the event in this node
+fn element_listeners<MSG>(
+    node: &crate::Node<MSG>,
+    event_name: &str,
+) -> (Listeners<MSG>, Listeners<MSG>)
+where
+    MSG: 'static,
+{
+    let mut capturing = vec![];
+    let mut bubbling = vec![];
+    let attrs: Vec<_> = node
+        .get_attributes()
+        .unwrap_or_default()
+        .iter()
+        .filter(|attr| *attr.name() == event_name)
+        .collect();
+    let mut index = 0;
+    for attr in mt_dom::merge_attributes_of_same_name(&attrs) {
+        let options = event_options(&attr);
+        let filters: Rc<[EventFilter]> = event_filters(&attr).into();
+        for callback in attr.get_callback() {
+            let listener =
+                (index, callback.clone(), options, Rc::clone(&filters));
+            if options.capture {
+                capturing.push(listener);
+            } else {
+                bubbling.push(listener);
+            }
+            index += 1;
+        }
+    }
+    (capturing, bubbling)
+}
+
+/// the paths of the nodes with these depth first indexes, which are used in the patches
+fn node_paths<MSG>(
+    vdom: &crate::Node<MSG>,
+    node_idxs: &BTreeSet<usize>,
+) -> HashMap<usize, Vec<usize>> {
+    fn visit<MSG>(
+        node: &crate::Node<MSG>,
+        path: &mut Vec<usize>,
+        cur_node_idx: &mut usize,
+        node_idxs: &BTreeSet<usize>,
+        paths: &mut HashMap<usize, Vec<usize>>,
+    ) {
+        if node_idxs.contains(cur_node_idx) {
+            paths.insert(*cur_node_idx, path.clone());
+        }
+        *cur_node_idx += 1;
+        for (index, child) in
+            node.get_children().unwrap_or_default().iter().enumerate()
+        {
+            path.push(index);
+            visit(child, path, cur_node_idx, node_idxs, paths);
+            path.pop();
+        }
+    }
+    let mut paths = HashMap::new();
+    visit(vdom, &mut vec![], &mut 0, node_idxs, &mut paths);
+    paths
+}
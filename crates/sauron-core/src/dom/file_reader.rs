@@ -0,0 +1,194 @@
+//! provides reading the content of the files chosen by the user as Cmds
+//!
+//! Example:
+//! ```rust,ignore
+//! input(
+//!     vec![r#type("file"), on_change(|event| Msg::FilesChosen(event.files))],
+//!     vec![],
+//! )
+//! ...
+//! Msg::FilesChosen(files) => {
+//!     FileReader::read(&files[0])
+//!         .on_progress(Msg::Progress)
+//!         .text(Msg::Loaded)
+//! }
+//! ```
+use crate::{dom::http::error_message, Cmd, Component, Dispatch, File};
+use std::rc::Rc;
+use thiserror::Error;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::ProgressEvent;
+
+/// Provides functions for reading the content of the files
+#[derive(Copy, Clone, Debug)]
+pub struct FileReader;
+
+/// A read of the file content which is built with `FileReader::read`
+/// and executed as a Cmd with `text`, `bytes` or `data_url`.
+pub struct Read<MSG> {
+    file: web_sys::File,
+    on_progress: Option<Rc<dyn Fn(Progress) -> MSG>>,
+}
+
+/// The progress of reading the file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// the number of bytes read so far
+    pub loaded: u64,
+    /// the size of the file in bytes, if it is known
+    pub total: Option<u64>,
+}
+
+/// The errors that can occur when reading the file
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum FileError {
+    /// the file can not be read, such as when it is removed
+    /// or the permission is revoked after it is chosen
+    #[error("Unable to read file: {0}")]
+    Read(String),
+}
+
+/// what the content of the file is read as
+#[derive(Debug, Clone, Copy)]
+enum ReadAs {
+    Text,
+    Bytes,
+    DataUrl,
+}
+
+impl FileReader {
+    /// start building the read of the file content
+    pub fn read<MSG>(file: &File) -> Read<MSG> {
+        Read {
+            file: file.as_web_sys().clone(),
+            on_progress: None,
+        }
+    }
+}
+
+impl<MSG> Read<MSG>
+where
+    MSG: 'static,
+{
+    /// emit the MSG from `f` with the progress as the file is being read
+    pub fn on_progress<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Progress) -> MSG + 'static,
+    {
+        self.on_progress = Some(Rc::new(f));
+        self
+    }
+
+    /// read the file as utf-8 text and emit the MSG from `f` with it
+    pub fn text<F, APP>(self, f: F) -> Cmd<APP, MSG>
+    where
+        F: Fn(Result<String, FileError>) -> MSG + 'static,
+        APP: Component<MSG> + 'static,
+    {
+        self.read_as(ReadAs::Text, move |result| {
+            f(result.map(|content| content.as_string().unwrap_or_default()))
+        })
+    }
+
+    /// read the file as bytes and emit the MSG from `f` with it
+    pub fn bytes<F, APP>(self, f: F) -> Cmd<APP, MSG>
+    where
+        F: Fn(Result<Vec<u8>, FileError>) -> MSG + 'static,
+        APP: Component<MSG> + 'static,
+    {
+        self.read_as(ReadAs::Bytes, move |result| {
+            f(
+                result
+                    .map(|content| js_sys::Uint8Array::new(&content).to_vec()),
+            )
+        })
+    }
+
+    /// read the file as a `data:` url, which can be used as the `src` of an image,
+    /// and emit the MSG from `f` with it
+    pub fn data_url<F, APP>(self, f: F) -> Cmd<APP, MSG>
+    where
+        F: Fn(Result<String, FileError>) -> MSG + 'static,
+        APP: Component<MSG> + 'static,
+    {
+        self.read_as(ReadAs::DataUrl, move |result| {
+            f(result.map(|content| content.as_string().unwrap_or_default()))
+        })
+    }
+
+    fn read_as<F, APP>(self, read_as: ReadAs, f: F) -> Cmd<APP, MSG>
+    where
+        F: Fn(Result<JsValue, FileError>) -> MSG + 'static,
+        APP: Component<MSG> + 'static,
+    {
+        let f = Rc::new(f);
+        Cmd::new(move |program| {
+            let reader =
+                web_sys::FileReader::new().expect("must have FileReader");
+
+            let started = match read_as {
+                ReadAs::Text => reader.read_as_text(&self.file),
+                ReadAs::Bytes => reader.read_as_array_buffer(&self.file),
+                ReadAs::DataUrl => reader.read_as_data_url(&self.file),
+            };
+            // the events are only dispatched after this returns,
+            // so the listeners are added once the read is started
+            // and nothing is leaked when it can not be started
+            if let Err(e) = started {
+                program.dispatch(f(Err(FileError::Read(error_message(&e)))));
+                return;
+            }
+
+            let on_progress = self.on_progress.clone().map(|on_progress| {
+                let program = Rc::clone(&program);
+                let closure: Closure<dyn FnMut(ProgressEvent)> =
+                    Closure::wrap(Box::new(move |event: ProgressEvent| {
+                        program.dispatch(on_progress(Progress::from(&event)));
+                    }));
+                reader.set_onprogress(Some(closure.as_ref().unchecked_ref()));
+                closure
+            });
+
+            // this is called once when the read succeeded, failed or is aborted,
+            // the progress closure is dropped together with it
+            let reader_clone = reader.clone();
+            let f = Rc::clone(&f);
+            let on_load_end = Closure::once_into_js(move || {
+                drop(on_progress);
+                let result = match reader_clone.error() {
+                    Some(error) => Err(FileError::Read(error.message())),
+                    None => reader_clone
+                        .result()
+                        .map_err(|e| FileError::Read(error_message(&e))),
+                };
+                program.dispatch(f(result));
+            });
+            reader.set_onloadend(Some(on_load_end.unchecked_ref()));
+        })
+    }
+}
+
+impl From<&ProgressEvent> for Progress {
+    fn from(event: &ProgressEvent) -> Self {
+        Progress {
+            loaded: event.loaded() as u64,
+            total: if event.length_computable() {
+                Some(event.total() as u64)
+            } else {
+                None
+            },
+        }
+    }
+}
+
+impl Progress {
+    /// the fraction of the file which is read, from 0.0 to 1.0
+    /// or None if the size is not known
+    pub fn fraction(&self) -> Option<f64> {
+        match self.total {
+            Some(0) => Some(1.0),
+            Some(total) => Some(self.loaded as f64 / total as f64),
+            None => None,
+        }
+    }
+}
@@ -1,14 +1,187 @@
 //! provides functions for retrieving data using http network request
-use crate::{mt_dom::Callback, Cmd, Component, Dispatch};
-use std::fmt::Debug;
+use crate::{Cmd, Component, Dispatch, File};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::Debug,
+    rc::Rc,
+};
+use thiserror::Error;
 use wasm_bindgen::{closure::Closure, JsCast, JsValue};
-use web_sys::Response;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    AbortController, FormData, Headers, RequestCredentials, RequestInit,
+};
 
 /// Provides functions for doing http network request
 #[derive(Copy, Clone, Debug)]
 pub struct Http;
 
+thread_local! {
+    /// The requests that are in-flight, keyed with their request key.
+    /// Each has the unique id of the request and its abort controller.
+    static IN_FLIGHT: RefCell<HashMap<String, (u64, AbortController)>> =
+        RefCell::new(HashMap::new());
+    static REQUEST_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+/// An http request which is built with the methods of `Http`
+/// and executed as a Cmd with `fetch`, `fetch_text` or `fetch_json`.
+///
+/// Example:
+/// ```rust,ignore
+/// Http::post("/api/submission")
+///     .header("Accept", "application/json")
+///     .json(&form_data)
+///     .timeout(5_000)
+///     .fetch_json(Msg::Submitted)
+/// ```
+#[derive(Debug, Clone)]
+pub struct Request {
+    method: &'static str,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<Body>,
+    credentials: Option<Credentials>,
+    timeout: Option<i32>,
+    key: Option<String>,
+    /// the error in building the request, which is reported when it is fetched
+    error: Option<HttpError>,
+}
+
+/// The body of the request
+#[derive(Debug, Clone, PartialEq)]
+pub enum Body {
+    /// plain text
+    Text(String),
+    /// serialized json, sent with `application/json` content type
+    Json(String),
+    /// name and value pairs, sent as `application/x-www-form-urlencoded`
+    Form(Vec<(String, String)>),
+    /// raw bytes
+    Bytes(Vec<u8>),
+    /// name and part pairs, sent as `multipart/form-data`,
+    /// which is used to upload the files
+    Multipart(Vec<(String, Part)>),
+}
+
+/// A field of the multipart body
+#[derive(Debug, Clone, PartialEq)]
+pub enum Part {
+    /// a text value
+    Text(String),
+    /// a file, which is sent with its file name
+    File(File),
+}
+
+/// Whether the browser sends the cookies and authorization headers with the request
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Credentials {
+    /// never send the credentials
+    Omit,
+    /// send the credentials only to the same origin, this is the browser default
+    SameOrigin,
+    /// always send the credentials, including cross-origin requests
+    Include,
+}
+
+/// The response of the http request
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    /// the final url of the response, after the redirects
+    pub url: String,
+    /// the http status code
+    pub status: u16,
+    /// the http status message
+    pub status_text: String,
+    /// the response headers, the header names are in lowercase
+    pub headers: Vec<(String, String)>,
+    /// the raw body of the response
+    pub body: Vec<u8>,
+}
+
+/// The errors that can occur when doing the http request
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum HttpError {
+    /// the request body can not be serialized
+    #[error("Unable to serialize request body: {0}")]
+    Serialize(String),
+    /// the request did not complete, such as when the network is down or
+    /// the request is blocked by CORS
+    #[error("Network error: {0}")]
+    Network(String),
+    /// the request did not complete within the timeout
+    #[error("Request timed out")]
+    Timeout,
+    /// the response has a status other than 2xx
+    #[error("Unexpected status {} {}", .0.status, .0.status_text)]
+    Status(Box<Response>),
+    /// the response body is not valid utf-8 text
+    #[error("Response is not valid utf-8: {0}")]
+    Utf8(String),
+    /// the response body can not be decoded from json
+    #[error("Unable to decode json response: {0}")]
+    Json(String),
+}
+
 impl Http {
+    /// create a request with the http `method` such as "GET", "POST", "OPTIONS"
+    pub fn request(method: &'static str, url: &str) -> Request {
+        Request {
+            method,
+            url: url.to_string(),
+            headers: vec![],
+            body: None,
+            credentials: None,
+            timeout: None,
+            key: None,
+            error: None,
+        }
+    }
+
+    /// create a GET request
+    pub fn get(url: &str) -> Request {
+        Self::request("GET", url)
+    }
+
+    /// create a POST request
+    pub fn post(url: &str) -> Request {
+        Self::request("POST", url)
+    }
+
+    /// create a PUT request
+    pub fn put(url: &str) -> Request {
+        Self::request("PUT", url)
+    }
+
+    /// create a PATCH request
+    pub fn patch(url: &str) -> Request {
+        Self::request("PATCH", url)
+    }
+
+    /// create a DELETE request
+    pub fn delete(url: &str) -> Request {
+        Self::request("DELETE", url)
+    }
+
+    /// abort the in-flight request which is tagged with the `key`,
+    /// its response will not be emitted
+    pub fn abort<APP, MSG>(key: &str) -> Cmd<APP, MSG>
+    where
+        APP: Component<MSG> + 'static,
+        MSG: 'static,
+    {
+        let key = key.to_string();
+        Cmd::new(move |_program| {
+            let in_flight =
+                IN_FLIGHT.with(|in_flight| in_flight.borrow_mut().remove(&key));
+            if let Some((_id, controller)) = in_flight {
+                controller.abort();
+            }
+        })
+    }
+
     /// fetch text document from the url and decode the result with the supplied
     /// response_text_decoder function
     pub fn fetch_with_text_response_decoder<DE, CB, OUT, APP, MSG>(
@@ -23,17 +196,11 @@ impl Http {
         APP: Component<MSG> + 'static,
         MSG: 'static,
     {
-        let response_text_decoder = Callback::from(response_text_decoder);
-        let cb = Callback::from(cb);
-        let cb_clone = cb.clone();
-        let response_decoder = move |js_value: JsValue| {
-            let response_text =
-                js_value.as_string().expect("There's no string value");
-            let msg_value = response_text_decoder.emit(response_text);
-            cb.emit(Ok(msg_value))
-        };
-        let fail_cb = move |js_value| cb_clone.emit(Err(js_value));
-        Self::fetch_with_response_decoder(url, response_decoder, fail_cb)
+        Self::get(url).fetch_text(move |result| {
+            cb(result
+                .map(&response_text_decoder)
+                .map_err(|e| JsValue::from_str(&e.to_string())))
+        })
     }
 
     /// API for fetching http rest request
@@ -48,66 +215,433 @@ impl Http {
         APP: Component<MSG> + 'static,
         MSG: 'static,
     {
-        let url_clone = url.to_string();
-        let response_decoder = Callback::from(response_decoder);
+        Self::get(url).fetch_text(move |result| match result {
+            Ok(text) => response_decoder(JsValue::from_str(&text)),
+            Err(e) => fail_cb(JsValue::from_str(&e.to_string())),
+        })
+    }
+}
 
-        let fail_cb = Callback::from(fail_cb);
+impl Request {
+    /// add a header to the request
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
 
-        let cmd: Cmd<APP, MSG> = Cmd::new(move |program| {
-            let program_clone = program.clone();
+    /// set the body of the request
+    pub fn body(mut self, body: Body) -> Self {
+        self.body = Some(body);
+        self
+    }
 
-            let response_decoder = response_decoder.clone();
+    /// set the body of the request to the text
+    pub fn text(self, text: &str) -> Self {
+        self.body(Body::Text(text.to_string()))
+    }
 
-            let decoder_and_dispatcher = move |js_value: JsValue| {
-                let msg = response_decoder.emit(js_value);
-                program_clone.dispatch(msg);
-            };
+    /// serialize the value into json and use it as the body of the request
+    pub fn json<T: Serialize>(mut self, value: &T) -> Self {
+        match serde_json::to_string(value) {
+            Ok(json) => self.body(Body::Json(json)),
+            Err(e) => {
+                self.error = Some(HttpError::Serialize(e.to_string()));
+                self
+            }
+        }
+    }
 
-            let fail_cb = fail_cb.clone();
-            let fail_cb2 = fail_cb.clone();
+    /// use the name and value pairs as a url encoded form body
+    pub fn form(self, fields: &[(&str, &str)]) -> Self {
+        self.body(Body::Form(
+            fields
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        ))
+    }
 
-            let program_clone_status_err = program.clone();
+    /// use the bytes as the body of the request
+    pub fn bytes(self, bytes: Vec<u8>) -> Self {
+        self.body(Body::Bytes(bytes))
+    }
 
-            let promise = crate::window().fetch_with_str(&url_clone);
-            let cb: Closure<dyn FnMut(JsValue)> =
-                Closure::once(move |js_value: JsValue| {
-                    let fail_cb = fail_cb.clone();
-                    let response: &Response = js_value.as_ref().unchecked_ref();
-                    let status = response.status();
-                    if status == 200 {
-                        let response_promise = response.text();
-                        if let Ok(response_promise) = response_promise {
-                            let decoder_and_dispatcher_cb: Closure<
-                                dyn FnMut(JsValue),
-                            > = Closure::once(decoder_and_dispatcher);
+    /// use the name and part pairs as a multipart form body,
+    /// ie: `.multipart(&[("title", Part::Text(title)), ("upload", Part::File(file))])`
+    pub fn multipart(self, fields: &[(&str, Part)]) -> Self {
+        self.body(Body::Multipart(
+            fields
+                .iter()
+                .map(|(name, part)| (name.to_string(), part.clone()))
+                .collect(),
+        ))
+    }
 
-                            let _ = response_promise
-                                .then(&decoder_and_dispatcher_cb);
+    /// set whether the cookies and authorization headers are sent with the request
+    pub fn credentials(mut self, credentials: Credentials) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
 
-                            decoder_and_dispatcher_cb.forget();
-                        } else {
-                            panic!("Expecting a string");
-                        }
+    /// abort the request when it has not completed after `millis` milliseconds
+    pub fn timeout(mut self, millis: i32) -> Self {
+        self.timeout = Some(millis);
+        self
+    }
+
+    /// tag the request with a key, fetching another request with the same key
+    /// aborts this request if it is still in-flight, and the response of
+    /// this request will no longer be emitted.
+    /// This is used to drop the stale responses, such as in search-as-you-type.
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.to_string());
+        self
+    }
+
+    /// execute the request and emit the MSG from `f` with the response.
+    /// Any status is considered a successful response here,
+    /// use `Response::error_for_status` to treat the non-2xx status as error.
+    pub fn fetch<F, APP, MSG>(self, f: F) -> Cmd<APP, MSG>
+    where
+        F: Fn(Result<Response, HttpError>) -> MSG + 'static,
+        APP: Component<MSG> + 'static,
+        MSG: 'static,
+    {
+        let f = Rc::new(f);
+        Cmd::new(move |program| {
+            let request = self.clone();
+            let f = Rc::clone(&f);
+            wasm_bindgen_futures::spawn_local(async move {
+                let controller =
+                    if request.key.is_some() || request.timeout.is_some() {
+                        Some(
+                            AbortController::new()
+                                .expect("must have AbortController"),
+                        )
                     } else {
-                        program_clone_status_err
-                            .dispatch(fail_cb.emit(js_value));
-                    }
+                        None
+                    };
+                let in_flight = request.key.as_ref().map(|key| {
+                    InFlight::start(
+                        key,
+                        controller.as_ref().expect("must have a controller"),
+                    )
                 });
+                let result = request.send(controller).await;
+                let is_current =
+                    in_flight.as_ref().is_none_or(InFlight::is_current);
+                drop(in_flight);
+                // the stale response of a superseded or aborted request is dropped
+                if is_current {
+                    program.dispatch(f(result));
+                }
+            });
+        })
+    }
 
-            let program_clone_response_error = program;
-            let fail_closure: Closure<dyn FnMut(JsValue)> =
-                Closure::once(move |js_value: JsValue| {
-                    let fail_cb = fail_cb2.clone();
-                    program_clone_response_error
-                        .dispatch(fail_cb.emit(js_value));
-                });
+    /// execute the request and emit the MSG from `f` with the response body as text,
+    /// a response with non-2xx status is an error.
+    pub fn fetch_text<F, APP, MSG>(self, f: F) -> Cmd<APP, MSG>
+    where
+        F: Fn(Result<String, HttpError>) -> MSG + 'static,
+        APP: Component<MSG> + 'static,
+        MSG: 'static,
+    {
+        self.fetch(move |result| {
+            f(result.and_then(|response| response.error_for_status()?.text()))
+        })
+    }
+
+    /// execute the request and emit the MSG from `f` with the response body
+    /// decoded from json, a response with non-2xx status is an error.
+    pub fn fetch_json<T, F, APP, MSG>(self, f: F) -> Cmd<APP, MSG>
+    where
+        T: DeserializeOwned,
+        F: Fn(Result<T, HttpError>) -> MSG + 'static,
+        APP: Component<MSG> + 'static,
+        MSG: 'static,
+    {
+        self.fetch(move |result| {
+            f(result.and_then(|response| response.error_for_status()?.json()))
+        })
+    }
 
-            let _ = promise.then(&cb).catch(&fail_closure);
+    /// execute the request in the browser
+    async fn send(
+        self,
+        controller: Option<AbortController>,
+    ) -> Result<Response, HttpError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let init = RequestInit::new();
+        init.set_method(self.method);
 
-            cb.forget();
-            fail_closure.forget();
+        let headers = Headers::new().expect("Unable to create headers");
+        if let Some(content_type) =
+            self.body.as_ref().and_then(Body::content_type)
+        {
+            if !self
+                .headers
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            {
+                headers
+                    .append("Content-Type", content_type)
+                    .expect("Unable to set content type");
+            }
+        }
+        for (name, value) in self.headers.iter() {
+            headers
+                .append(name, value)
+                .map_err(|e| HttpError::Network(error_message(&e)))?;
+        }
+        init.set_headers(&headers);
+
+        if let Some(body) = &self.body {
+            init.set_body(&body.to_js_value());
+        }
+        if let Some(credentials) = self.credentials {
+            init.set_credentials(credentials.into());
+        }
+
+        if let Some(controller) = &controller {
+            init.set_signal(Some(&controller.signal()));
+        }
+        // the timer is cleared when this is dropped
+        let timeout = match (self.timeout, controller) {
+            (Some(millis), Some(controller)) => {
+                Some(Timeout::start(millis, controller))
+            }
+            _ => None,
+        };
+        let map_err = |e: JsValue| match &timeout {
+            Some(timeout) if timeout.is_expired() => HttpError::Timeout,
+            _ => HttpError::Network(error_message(&e)),
+        };
+
+        let response = JsFuture::from(
+            crate::window().fetch_with_str_and_init(&self.url, &init),
+        )
+        .await
+        .map_err(map_err)?;
+        let response: web_sys::Response = response.unchecked_into();
+        let headers = js_sys::try_iter(&response.headers())
+            .ok()
+            .flatten()
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(|entry| {
+                        let entry: js_sys::Array = entry.unchecked_into();
+                        (
+                            entry.get(0).as_string().unwrap_or_default(),
+                            entry.get(1).as_string().unwrap_or_default(),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let buffer = response.array_buffer().map_err(map_err)?;
+        let buffer = JsFuture::from(buffer).await.map_err(map_err)?;
+        Ok(Response {
+            url: response.url(),
+            status: response.status(),
+            status_text: response.status_text(),
+            headers,
+            body: js_sys::Uint8Array::new(&buffer).to_vec(),
+        })
+    }
+}
+
+impl Body {
+    /// the default content type of this body
+    fn content_type(&self) -> Option<&'static str> {
+        match self {
+            Body::Text(_) => Some("text/plain;charset=UTF-8"),
+            Body::Json(_) => Some("application/json"),
+            Body::Form(_) => Some("application/x-www-form-urlencoded"),
+            // the browser sets the content type together with the boundary
+            Body::Bytes(_) | Body::Multipart(_) => None,
+        }
+    }
+
+    fn to_js_value(&self) -> JsValue {
+        match self {
+            Body::Text(text) | Body::Json(text) => JsValue::from_str(text),
+            Body::Form(fields) => {
+                let encoded: Vec<String> = fields
+                    .iter()
+                    .map(|(name, value)| {
+                        format!(
+                            "{}={}",
+                            crate::router::encode(name),
+                            crate::router::encode(value)
+                        )
+                    })
+                    .collect();
+                JsValue::from_str(&encoded.join("&"))
+            }
+            Body::Bytes(bytes) => js_sys::Uint8Array::from(&bytes[..]).into(),
+            Body::Multipart(fields) => {
+                let form_data =
+                    FormData::new().expect("Unable to create form data");
+                for (name, part) in fields {
+                    match part {
+                        Part::Text(value) => {
+                            form_data.append_with_str(name, value)
+                        }
+                        Part::File(file) => form_data
+                            .append_with_blob_and_filename(
+                                name,
+                                file.as_web_sys(),
+                                file.name(),
+                            ),
+                    }
+                    .expect("Unable to append to form data");
+                }
+                form_data.into()
+            }
+        }
+    }
+}
+
+impl From<Credentials> for RequestCredentials {
+    fn from(credentials: Credentials) -> Self {
+        match credentials {
+            Credentials::Omit => RequestCredentials::Omit,
+            Credentials::SameOrigin => RequestCredentials::SameOrigin,
+            Credentials::Include => RequestCredentials::Include,
+        }
+    }
+}
+
+impl Response {
+    /// returns true if the status is 2xx
+    pub fn is_ok(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// returns the response as is if the status is 2xx,
+    /// otherwise the response is returned as the `HttpError::Status`
+    pub fn error_for_status(self) -> Result<Self, HttpError> {
+        if self.is_ok() {
+            Ok(self)
+        } else {
+            Err(HttpError::Status(Box::new(self)))
+        }
+    }
+
+    /// the value of the header, the name is case-insensitive
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// the body as text
+    pub fn text(&self) -> Result<String, HttpError> {
+        String::from_utf8(self.body.clone())
+            .map_err(|e| HttpError::Utf8(e.to_string()))
+    }
+
+    /// decode the json body into `T`
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, HttpError> {
+        serde_json::from_slice(&self.body)
+            .map_err(|e| HttpError::Json(e.to_string()))
+    }
+}
+
+/// A keyed request which is in-flight.
+/// It is no longer current when another request with the same key has started,
+/// or when it is aborted with `Http::abort`.
+struct InFlight {
+    key: String,
+    id: u64,
+}
+
+impl InFlight {
+    /// register the request, aborting the previous request with the same key
+    fn start(key: &str, controller: &AbortController) -> Self {
+        let id = REQUEST_ID.with(|request_id| {
+            request_id.set(request_id.get() + 1);
+            request_id.get()
+        });
+        let previous = IN_FLIGHT.with(|in_flight| {
+            in_flight
+                .borrow_mut()
+                .insert(key.to_string(), (id, controller.clone()))
         });
+        if let Some((_id, previous_controller)) = previous {
+            previous_controller.abort();
+        }
+        InFlight {
+            key: key.to_string(),
+            id,
+        }
+    }
+
+    fn is_current(&self) -> bool {
+        IN_FLIGHT.with(|in_flight| {
+            matches!(in_flight.borrow().get(&self.key), Some((id, _)) if *id == self.id)
+        })
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        if self.is_current() {
+            IN_FLIGHT
+                .with(|in_flight| in_flight.borrow_mut().remove(&self.key));
+        }
+    }
+}
+
+/// aborts the request when the timer expires, the timer is cleared when this is dropped
+struct Timeout {
+    handle: i32,
+    expired: Rc<Cell<bool>>,
+    _closure: Closure<dyn FnMut()>,
+}
+
+impl Timeout {
+    fn start(millis: i32, controller: AbortController) -> Self {
+        let expired = Rc::new(Cell::new(false));
+        let expired_clone = Rc::clone(&expired);
+        let closure: Closure<dyn FnMut()> =
+            Closure::wrap(Box::new(move || {
+                expired_clone.set(true);
+                controller.abort();
+            }));
+        let handle = crate::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                closure.as_ref().unchecked_ref(),
+                millis,
+            )
+            .expect("Unable to start timeout");
+        Timeout {
+            handle,
+            expired,
+            _closure: closure,
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        self.expired.get()
+    }
+}
+
+impl Drop for Timeout {
+    fn drop(&mut self) {
+        crate::window().clear_timeout_with_handle(self.handle);
+    }
+}
 
-        cmd
+/// the message of the javascript error
+pub(crate) fn error_message(error: &JsValue) -> String {
+    match error.dyn_ref::<js_sys::Error>() {
+        Some(error) => String::from(error.message()),
+        None => error.as_string().unwrap_or_else(|| format!("{:?}", error)),
     }
 }
@@ -0,0 +1,264 @@
+//! Hydration attaches the app view to an existing DOM tree, such as the markup
+//! that was rendered in the server, instead of recreating it.
+//!
+//! The existing DOM is walked side by side with the virtual node.
+//! Event listeners are attached to the elements that are already there,
+//! while the parts where the server and the client disagree are fixed up in place.
+//! Each of the disagreement is logged as a warning so it can be traced back
+//! to the view code that produced it.
+use crate::{
+    dom::{
+        created_node::{ActiveClosure, CreatedNode, DATA_SAURON_VDOM_ID},
+        sub_component::{mount_sub_component, DATA_SAURON_COMPONENT},
+    },
+    Dispatch,
+};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, Node, Text};
+
+/// Hydrate the existing `dom_node` with the supplied `vnode`,
+/// the closures of the attached event listeners are accumulated in `closures`.
+/// No event listeners are attached when there is no program, such as when the events are delegated.
+/// Returns the DOM node that now corresponds to the `vnode`, which is a different node
+/// when the existing node has to be replaced.
+pub(crate) fn hydrate_node<DSP, MSG>(
+    program: Option<&DSP>,
+    vnode: &crate::Node<MSG>,
+    dom_node: &Node,
+    closures: &mut ActiveClosure,
+    path: &str,
+) -> Node
+where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    match vnode {
+        crate::Node::Text(text) if dom_node.node_type() == Node::TEXT_NODE => {
+            let dom_text = dom_node.node_value().unwrap_or_default();
+            if dom_text != *text {
+                // whitespace around the text is an artifact of the indented render
+                if dom_text.trim() != text.trim() {
+                    mismatch(
+                        path,
+                        &format!("text {:?}", text),
+                        &format!("text {:?}", dom_text),
+                    );
+                }
+                dom_node.set_node_value(Some(text));
+            }
+            dom_node.clone()
+        }
+        crate::Node::Element(velem)
+            if dom_node.node_type() == Node::ELEMENT_NODE
+                && is_same_tag(dom_node.unchecked_ref(), velem.tag()) =>
+        {
+            hydrate_element(
+                program,
+                velem,
+                dom_node.unchecked_ref(),
+                closures,
+                path,
+            );
+            dom_node.clone()
+        }
+        _ => {
+            mismatch(path, &describe_vnode(vnode), &describe_dom(dom_node));
+            let created_node =
+                CreatedNode::<Node>::create_dom_node_opt(program, vnode);
+            let dom_element: &Element = dom_node.unchecked_ref();
+            dom_element
+                .replace_with_with_node_1(&created_node.node)
+                .expect("Unable to replace mismatched node");
+            closures.extend(created_node.closures);
+            created_node.node
+        }
+    }
+}
+
+/// attach the event listeners and fix the attributes and children of this element
+fn hydrate_element<DSP, MSG>(
+    program: Option<&DSP>,
+    velem: &crate::Element<MSG>,
+    element: &Element,
+    closures: &mut ActiveClosure,
+    path: &str,
+) where
+    MSG: 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let attrs = mt_dom::merge_attributes_of_same_name(
+        &velem.get_attributes().iter().collect::<Vec<_>>(),
+    );
+    for att in attrs.iter() {
+        // empty attributes are not rendered
+        if att.name().is_empty() {
+            continue;
+        }
+        let plain_values: Vec<_> = att
+            .get_plain()
+            .into_iter()
+            .filter(|v| !v.is_function_call())
+            .collect();
+        let expected = CreatedNode::<Node>::merge_plain_attributes_values(
+            att.name(),
+            &plain_values,
+        );
+        let existing = element.get_attribute(att.name());
+        if expected.is_some() && expected != existing {
+            mismatch(
+                path,
+                &format!("{}={:?}", att.name(), expected.unwrap_or_default()),
+                &format!("{}={:?}", att.name(), existing),
+            );
+        }
+        // this also attach the event listeners of the attribute
+        CreatedNode::<Node>::set_element_attribute(
+            program, closures, element, att,
+        );
+    }
+
+    // remove the attributes that are not in the view
+    let attribute_names = element.get_attribute_names();
+    for name in attribute_names.iter() {
+        let name = name.as_string().expect("must be a string");
+        if name != DATA_SAURON_VDOM_ID
+            && !attrs.iter().any(|att| *att.name() == name)
+        {
+            mismatch(path, "no attribute", &format!("attribute {:?}", name));
+            element
+                .remove_attribute(&name)
+                .expect("Unable to remove attribute");
+        }
+    }
+
+    // the content of the sub component is moved in from its own program
+    if let Some(component_id) = element.get_attribute(DATA_SAURON_COMPONENT) {
+        element.set_inner_html("");
+        mount_sub_component(&component_id, element);
+        return;
+    }
+
+    let children = velem.get_children();
+    let mut dom_child = element.first_child();
+    for (i, vchild) in children.iter().enumerate() {
+        let child_path = format!("{}/{}[{}]", path, describe_tag(vchild), i);
+        dom_child = skip_insignificant_nodes(dom_child, vchild.is_text());
+        match dom_child {
+            Some(ref current) => {
+                // a text node missing in the DOM, which usually
+                // happens when adjacent text nodes are merged by the html parser.
+                if vchild.is_text() && current.node_type() != Node::TEXT_NODE {
+                    mismatch(
+                        &child_path,
+                        &describe_vnode(vchild),
+                        &describe_dom(current),
+                    );
+                    let created_node = CreatedNode::<Node>::create_dom_node_opt(
+                        program, vchild,
+                    );
+                    element
+                        .insert_before(&created_node.node, Some(current))
+                        .expect("Unable to insert node");
+                    continue;
+                }
+                let hydrated = hydrate_node(
+                    program,
+                    vchild,
+                    current,
+                    closures,
+                    &child_path,
+                );
+                dom_child = hydrated.next_sibling();
+            }
+            None => {
+                mismatch(&child_path, &describe_vnode(vchild), "nothing");
+                let created_node =
+                    CreatedNode::<Node>::create_dom_node_opt(program, vchild);
+                element
+                    .append_child(&created_node.node)
+                    .expect("Unable to append node");
+                closures.extend(created_node.closures);
+            }
+        }
+    }
+
+    // remove the excess nodes which are not in the view
+    while let Some(current) = skip_insignificant_nodes(dom_child, false) {
+        dom_child = current.next_sibling();
+        mismatch(path, "no more child", &describe_dom(&current));
+        element
+            .remove_child(&current)
+            .expect("Unable to remove child");
+    }
+}
+
+/// skip over the comment separators and remove the whitespace-only text nodes
+/// which are produced by the indention of the rendered html.
+/// The whitespace is left intact when a text node is expected at this position.
+fn skip_insignificant_nodes(
+    mut dom_child: Option<Node>,
+    expecting_text: bool,
+) -> Option<Node> {
+    while let Some(current) = dom_child {
+        let next = current.next_sibling();
+        match current.node_type() {
+            Node::COMMENT_NODE => (),
+            Node::TEXT_NODE if !expecting_text && is_whitespace(&current) => {
+                let parent = current.parent_node().expect("must have a parent");
+                parent
+                    .remove_child(&current)
+                    .expect("Unable to remove whitespace");
+            }
+            _ => return Some(current),
+        }
+        dom_child = next;
+    }
+    None
+}
+
+fn is_whitespace(node: &Node) -> bool {
+    let text: &Text = node.unchecked_ref();
+    text.data().trim().is_empty()
+}
+
+/// html element tag names are reported in uppercase by the browser,
+/// while svg tag names are kept as is.
+fn is_same_tag(element: &Element, tag: &str) -> bool {
+    element.tag_name().eq_ignore_ascii_case(tag)
+}
+
+fn describe_tag<MSG>(vnode: &crate::Node<MSG>) -> &str {
+    match vnode {
+        crate::Node::Element(velem) => velem.tag(),
+        crate::Node::Text(_) => "text",
+    }
+}
+
+fn describe_vnode<MSG>(vnode: &crate::Node<MSG>) -> String {
+    match vnode {
+        crate::Node::Element(velem) => format!("<{}>", velem.tag()),
+        crate::Node::Text(text) => format!("text {:?}", text),
+    }
+}
+
+fn describe_dom(node: &Node) -> String {
+    match node.node_type() {
+        Node::ELEMENT_NODE => {
+            let element: &Element = node.unchecked_ref();
+            format!("<{}>", element.tag_name().to_lowercase())
+        }
+        Node::TEXT_NODE => {
+            format!("text {:?}", node.node_value().unwrap_or_default())
+        }
+        other => format!("node type {}", other),
+    }
+}
+
+fn mismatch(path: &str, expected: &str, found: &str) {
+    log::warn!(
+        "hydration mismatch at {}: expecting {}, but found {} in the DOM",
+        path,
+        expected,
+        found
+    );
+}
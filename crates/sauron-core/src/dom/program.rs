@@ -1,9 +1,32 @@
-use crate::{dom::dom_updater::DomUpdater, Cmd, Component, Dispatch};
-use std::{cell::RefCell, rc::Rc};
+use crate::{
+    dom::{
+        dom_updater::DomUpdater, subscription::ActiveSubscriptions, Storage,
+        StorageArea,
+    },
+    initial_state::{read_initial_state, InitialStateError},
+    Cmd, Component, Dispatch,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    any::TypeId,
+    cell::RefCell,
+    collections::HashSet,
+    rc::{Rc, Weak},
+};
 #[cfg(feature = "with-request-animation-frame")]
 use wasm_bindgen::closure::Closure;
 use web_sys::Node;
 
+/// saves the app into the storage, set with `persist_model`
+type PersistFn<APP> = Rc<dyn Fn(&APP)>;
+
+thread_local! {
+    /// The component types which styles are already injected into the document,
+    /// so the programs of the same component such as the sub components don't repeat them.
+    static INJECTED_STYLES: RefCell<HashSet<TypeId>> =
+        RefCell::new(HashSet::new());
+}
+
 /// Holds the user App and the dom updater
 /// This is passed into the event listener and the dispatch program
 /// will be called after the event is triggered.
@@ -17,6 +40,10 @@ where
     pub app: Rc<RefCell<APP>>,
     /// The dom_updater responsible to updating the actual document in the browser
     pub dom_updater: Rc<RefCell<DomUpdater<MSG>>>,
+    /// the listeners of the subscriptions of the app
+    subscriptions: Rc<RefCell<ActiveSubscriptions<MSG>>>,
+    /// saves the app into the storage after every update, set with `persist_model`
+    persist: Rc<RefCell<Option<PersistFn<APP>>>>,
 }
 
 impl<APP, MSG> Clone for Program<APP, MSG>
@@ -27,6 +54,8 @@ where
         Program {
             app: Rc::clone(&self.app),
             dom_updater: Rc::clone(&self.dom_updater),
+            subscriptions: Rc::clone(&self.subscriptions),
+            persist: Rc::clone(&self.persist),
         }
     }
 }
@@ -44,25 +73,53 @@ where
         let program = Program {
             app: Rc::new(RefCell::new(app)),
             dom_updater: Rc::new(RefCell::new(dom_updater)),
+            subscriptions: Rc::new(RefCell::new(ActiveSubscriptions::new())),
+            persist: Rc::new(RefCell::new(None)),
         };
         program
     }
 
     /// executed after the program has been mounted
     fn after_mounted(&self) {
-        for style in self.app.borrow().style() {
-            Self::inject_style(&style);
+        let is_first_of_type = INJECTED_STYLES
+            .with(|injected| injected.borrow_mut().insert(TypeId::of::<APP>()));
+        if is_first_of_type {
+            for style in self.app.borrow().style() {
+                Self::inject_style(&style);
+            }
         }
         // call the init of the component
         let cmds: Cmd<APP, MSG> = self.app.borrow().init();
         // then emit the cmds, so it starts executing initial calls such (ie: fetching data,
         // listening to events (resize, hashchange)
-        cmds.emit(&self);
+        cmds.emit(self);
+        self.update_subscriptions();
+    }
+
+    /// re-evaluate the subscriptions of the app, adding the new listeners
+    /// and removing the ones that are no longer subscribed to
+    fn update_subscriptions(&self) {
+        let sub = self.app.borrow().subscriptions();
+        self.subscriptions.borrow_mut().update(self, sub);
+    }
+
+    /// Save the app as json into the storage with `key` after every update,
+    /// the saved app can be read back with `Storage::get` when the app is created.
+    /// The errors in saving are logged.
+    pub fn persist_model(&self, area: StorageArea, key: &str)
+    where
+        APP: Serialize,
+    {
+        let key = key.to_string();
+        *self.persist.borrow_mut() = Some(Rc::new(move |app: &APP| {
+            if let Err(e) = Storage::set(area, &key, app) {
+                log::error!("Unable to persist the app to {:?}: {}", key, e);
+            }
+        }));
     }
 
     /// get the real DOM node where this app is mounted to.
-    #[allow(unused)]
-    fn root_node(&self) -> web_sys::Node {
+    pub(crate) fn root_node(&self) -> web_sys::Node {
         self.dom_updater.borrow().root_node()
     }
 
@@ -82,11 +139,77 @@ where
         program
     }
 
+    /// Instantiate the app and hydrate the existing DOM in root_node with the app view.
+    /// The root_node is the top-level element of the html that was rendered from the app view,
+    /// usually in the server. The existing elements are reused and only
+    /// the mismatched parts are fixed up.
+    pub fn hydrate(app: APP, root_node: &Node) -> Self {
+        let program = Self::new(app, root_node);
+        program.start_hydrate();
+        program.after_mounted();
+        program
+    }
+
+    /// Instantiate the app from the initial state which was embedded in the document
+    /// using `initial_state::render_with_initial_state`, then hydrate the existing DOM in root_node.
+    pub fn hydrate_with_initial_state(
+        root_node: &Node,
+    ) -> Result<Self, InitialStateError>
+    where
+        APP: DeserializeOwned,
+    {
+        let app: APP = read_initial_state()?;
+        Ok(Self::hydrate(app, root_node))
+    }
+
     /// Instantiate the app and then append it to the document body
     pub fn mount_to_body(app: APP) -> Self {
         Self::new_append_to_mount(app, &crate::body())
     }
 
+    /// Instantiate the app and append the view to the root_node, with the events delegated
+    /// to a single listener for each event type at the root of the view,
+    /// instead of attaching a closure to each element which has an event listener.
+    /// This uses less memory and speeds up the patching of views with a lot of listeners,
+    /// such as big tables.
+    pub fn new_append_to_mount_delegated(app: APP, root_node: &Node) -> Self {
+        let program = Self::new(app, root_node);
+        program.delegate_events();
+        program.start_append_to_mount();
+        program.after_mounted();
+        program
+    }
+
+    /// Instantiate the app and replace the root_node with the app view,
+    /// with the events delegated at the root of the view
+    pub fn new_replace_mount_delegated(app: APP, root_node: &Node) -> Self {
+        let program = Self::new(app, root_node);
+        program.delegate_events();
+        program.start_replace_mount();
+        program.after_mounted();
+        program
+    }
+
+    /// Instantiate the app and hydrate the existing DOM in root_node with the app view,
+    /// with the events delegated at the root of the view
+    pub fn hydrate_delegated(app: APP, root_node: &Node) -> Self {
+        let program = Self::new(app, root_node);
+        program.delegate_events();
+        program.start_hydrate();
+        program.after_mounted();
+        program
+    }
+
+    /// Instantiate the app and then append it to the document body,
+    /// with the events delegated at the root of the view
+    pub fn mount_to_body_delegated(app: APP) -> Self {
+        Self::new_append_to_mount_delegated(app, &crate::body())
+    }
+
+    fn delegate_events(&self) {
+        self.dom_updater.borrow_mut().delegate_events()
+    }
+
     fn start_append_to_mount(&self) {
         self.dom_updater.borrow_mut().append_to_mount(self)
     }
@@ -95,17 +218,28 @@ where
         self.dom_updater.borrow_mut().replace_mount(self)
     }
 
+    fn start_hydrate(&self) {
+        self.dom_updater.borrow_mut().hydrate(self)
+    }
+
     /// This is called when an event is triggered in the html DOM.
     /// The sequence of things happening here:
     /// - The app component update is executed.
+    /// - The app is saved into the storage, if enabled with `persist_model`.
     /// - The returned Cmd from the component update is then emitted.
     /// - The view is reconstructed with the new state of the app.
     /// - The dom is updated with the newly reconstructed view.
+    /// - The subscriptions are re-evaluated with the new state of the app.
     fn dispatch_inner(&self, msg: MSG) {
         #[cfg(feature = "with-measure")]
         let t1 = crate::now();
         // update the app and emit the cmd returned from the update
         let cmd = self.app.borrow_mut().update(msg);
+        // clone the hook out, so it can call persist_model again
+        let persist = self.persist.borrow().clone();
+        if let Some(persist) = persist {
+            persist(&self.app.borrow());
+        }
         cmd.emit(self);
         //trace!("Executing cmd..");
         #[cfg(feature = "with-measure")]
@@ -124,6 +258,7 @@ where
         };
         // update the last DOM node tree with this new view
         self.dom_updater.borrow_mut().update_dom(self, view);
+        self.update_subscriptions();
         #[cfg(feature = "with-measure")]
         {
             let t4 = crate::now();
@@ -167,4 +302,60 @@ where
     fn dispatch(&self, msg: MSG) {
         self.dispatch_inner(msg)
     }
+
+    fn downgrade(&self) -> Option<Rc<dyn Dispatch<MSG>>> {
+        Some(Rc::new(WeakProgram {
+            app: Rc::downgrade(&self.app),
+            dom_updater: Rc::downgrade(&self.dom_updater),
+            subscriptions: Rc::downgrade(&self.subscriptions),
+            persist: Rc::downgrade(&self.persist),
+        }))
+    }
+}
+
+/// A program which doesn't keep the app alive,
+/// the msgs dispatched after the program is dropped are ignored
+struct WeakProgram<APP, MSG>
+where
+    MSG: 'static,
+{
+    app: Weak<RefCell<APP>>,
+    dom_updater: Weak<RefCell<DomUpdater<MSG>>>,
+    subscriptions: Weak<RefCell<ActiveSubscriptions<MSG>>>,
+    persist: Weak<RefCell<Option<PersistFn<APP>>>>,
+}
+
+impl<APP, MSG> WeakProgram<APP, MSG>
+where
+    MSG: 'static,
+{
+    fn upgrade(&self) -> Option<Program<APP, MSG>> {
+        Some(Program {
+            app: self.app.upgrade()?,
+            dom_updater: self.dom_updater.upgrade()?,
+            subscriptions: self.subscriptions.upgrade()?,
+            persist: self.persist.upgrade()?,
+        })
+    }
+}
+
+impl<APP, MSG> Dispatch<MSG> for WeakProgram<APP, MSG>
+where
+    MSG: 'static,
+    APP: Component<MSG> + 'static,
+{
+    fn dispatch(&self, msg: MSG) {
+        if let Some(program) = self.upgrade() {
+            program.dispatch(msg);
+        }
+    }
+
+    fn downgrade(&self) -> Option<Rc<dyn Dispatch<MSG>>> {
+        Some(Rc::new(WeakProgram {
+            app: Weak::clone(&self.app),
+            dom_updater: Weak::clone(&self.dom_updater),
+            subscriptions: Weak::clone(&self.subscriptions),
+            persist: Weak::clone(&self.persist),
+        }))
+    }
 }
@@ -0,0 +1,191 @@
+//! provides functions for persisting serde values in the localStorage or sessionStorage of the browser
+use crate::{Cmd, Component, Sub};
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::StorageEvent;
+
+/// Provides functions for reading and writing serde values
+/// into the localStorage or sessionStorage as json.
+///
+/// Example:
+/// ```rust,ignore
+/// let model = Storage::get(StorageArea::Local, "todomvc::data")
+///     .ok()
+///     .flatten()
+///     .unwrap_or_else(Model::new);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Storage;
+
+/// Which storage of the browser is used
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StorageArea {
+    /// the localStorage, which persists across browser sessions and is shared by the tabs
+    Local,
+    /// the sessionStorage, which is cleared when the tab is closed
+    Session,
+}
+
+/// The errors that can occur when using the storage
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum StorageError {
+    /// the storage is not available, such as when it is disabled by the browser settings
+    #[error("Storage is not available")]
+    Unavailable,
+    /// the value can not be serialized into json
+    #[error("Unable to serialize value: {0}")]
+    Serialize(String),
+    /// the stored value can not be deserialized from json
+    #[error("Unable to deserialize value: {0}")]
+    Deserialize(String),
+    /// the value can not be written, such as when the storage quota is exceeded
+    #[error("Unable to write to storage: {0}")]
+    Write(String),
+}
+
+impl Storage {
+    /// read and deserialize the value stored with `key`,
+    /// returns None if there is no value stored with that key
+    pub fn get<T>(
+        area: StorageArea,
+        key: &str,
+    ) -> Result<Option<T>, StorageError>
+    where
+        T: DeserializeOwned,
+    {
+        let json = area
+            .storage()?
+            .get_item(key)
+            .map_err(|_| StorageError::Unavailable)?;
+        json.map(|json| deserialize(&json)).transpose()
+    }
+
+    /// serialize and store the value with `key`
+    pub fn set<T>(
+        area: StorageArea,
+        key: &str,
+        value: &T,
+    ) -> Result<(), StorageError>
+    where
+        T: Serialize,
+    {
+        let json = serde_json::to_string(value)
+            .map_err(|e| StorageError::Serialize(e.to_string()))?;
+        area.storage()?
+            .set_item(key, &json)
+            .map_err(|e| StorageError::Write(format!("{:?}", e)))
+    }
+
+    /// remove the value stored with `key`
+    pub fn remove(area: StorageArea, key: &str) -> Result<(), StorageError> {
+        area.storage()?
+            .remove_item(key)
+            .map_err(|e| StorageError::Write(format!("{:?}", e)))
+    }
+
+    /// read the value stored with `key` and emit the MSG from `f` with it
+    pub fn load<T, F, APP, MSG>(
+        area: StorageArea,
+        key: &str,
+        f: F,
+    ) -> Cmd<APP, MSG>
+    where
+        T: DeserializeOwned,
+        F: Fn(Result<Option<T>, StorageError>) -> MSG + 'static,
+        APP: Component<MSG> + 'static,
+        MSG: 'static,
+    {
+        let key = key.to_string();
+        Cmd::new(move |program| program.dispatch(f(Self::get(area, &key))))
+    }
+
+    /// store the value with `key`, the value is serialized when the Cmd is created
+    /// and the errors are logged
+    pub fn save<T, APP, MSG>(
+        area: StorageArea,
+        key: &str,
+        value: &T,
+    ) -> Cmd<APP, MSG>
+    where
+        T: Serialize,
+        APP: Component<MSG> + 'static,
+        MSG: 'static,
+    {
+        let key = key.to_string();
+        let json = serde_json::to_string(value)
+            .map_err(|e| StorageError::Serialize(e.to_string()));
+        Cmd::new(move |_program| {
+            let result = json.clone().and_then(|json| {
+                area.storage()?
+                    .set_item(&key, &json)
+                    .map_err(|e| StorageError::Write(format!("{:?}", e)))
+            });
+            if let Err(e) = result {
+                log::error!("Unable to save {:?} to storage: {}", key, e);
+            }
+        })
+    }
+
+    /// remove the value stored with `key`, the errors are logged
+    pub fn delete<APP, MSG>(area: StorageArea, key: &str) -> Cmd<APP, MSG>
+    where
+        APP: Component<MSG> + 'static,
+        MSG: 'static,
+    {
+        let key = key.to_string();
+        Cmd::new(move |_program| {
+            if let Err(e) = Self::remove(area, &key) {
+                log::error!("Unable to remove {:?} from storage: {}", key, e);
+            }
+        })
+    }
+
+    /// emit the MSG when the value with `key` is changed by another tab or window.
+    /// The function is supplied with the new value, which is None when the value is removed.
+    /// This is used for syncing the state across the tabs.
+    pub fn on_change<T, F, MSG>(area: StorageArea, key: &str, f: F) -> Sub<MSG>
+    where
+        T: DeserializeOwned,
+        F: Fn(Result<Option<T>, StorageError>) -> MSG + 'static,
+        MSG: 'static,
+    {
+        let key = key.to_string();
+        Sub::on_window_filter_map("storage", move |event| {
+            let event: StorageEvent = event.unchecked_into();
+            let is_area = match (event.storage_area(), area.storage()) {
+                (Some(changed), Ok(storage)) => {
+                    JsValue::from(changed) == JsValue::from(storage)
+                }
+                _ => false,
+            };
+            // the key is None when the whole storage is cleared
+            let is_key = event.key().is_none_or(|k| k == key);
+            if is_area && is_key {
+                Some(f(event
+                    .new_value()
+                    .map(|json| deserialize(&json))
+                    .transpose()))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl StorageArea {
+    /// the storage of the browser for this area
+    pub fn storage(&self) -> Result<web_sys::Storage, StorageError> {
+        let window = crate::window();
+        let storage = match self {
+            StorageArea::Local => window.local_storage(),
+            StorageArea::Session => window.session_storage(),
+        };
+        storage.ok().flatten().ok_or(StorageError::Unavailable)
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(json: &str) -> Result<T, StorageError> {
+    serde_json::from_str(json)
+        .map_err(|e| StorageError::Deserialize(e.to_string()))
+}
@@ -0,0 +1,138 @@
+//! Provides a way to embed a stateful `Component` in the view of another component.
+//!
+//! The sub component is driven by its own `Program`, so its state, `init` Cmds and `style`
+//! are managed by the runtime and updating it only diffs and patches its own subtree.
+//! The parent view only contains an empty placeholder element, which is skipped
+//! when the parent DOM is patched.
+use crate::{html::attributes::attr, Component, Dispatch, Node, Program};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use web_sys::Element;
+
+/// The attribute which marks an element as the placeholder of a sub component.
+/// The value is the unique id of the sub component.
+pub(crate) const DATA_SAURON_COMPONENT: &str = "data-sauron-component";
+
+/// mounts the sub component into the placeholder element
+type Mount = Rc<dyn Fn(&Element)>;
+
+thread_local! {
+    /// The mount functions of the live sub components, keyed with their unique id.
+    static MOUNT_POINTS: RefCell<HashMap<u32, Mount>> =
+        RefCell::new(HashMap::new());
+    static COMPONENT_ID: RefCell<u32> = const { RefCell::new(0) };
+}
+
+fn create_unique_identifier() -> u32 {
+    COMPONENT_ID.with(|id| {
+        *id.borrow_mut() += 1;
+        *id.borrow()
+    })
+}
+
+/// mount the sub component with `component_id` into the placeholder element
+pub(crate) fn mount_sub_component(component_id: &str, element: &Element) {
+    let mount = component_id.parse::<u32>().ok().and_then(|id| {
+        MOUNT_POINTS
+            .with(|mount_points| mount_points.borrow().get(&id).cloned())
+    });
+    match mount {
+        Some(mount) => mount(element),
+        None => log::warn!(
+            "There is no sub component with the id: {}",
+            component_id
+        ),
+    }
+}
+
+/// returns true if the element is a placeholder of a sub component
+pub(crate) fn is_sub_component(element: &Element) -> bool {
+    element.has_attribute(DATA_SAURON_COMPONENT)
+}
+
+/// A stateful component which is embedded in the view of a parent component.
+///
+/// The SubComponent is created once and stored in the parent model,
+/// its `view` is then placed in the parent view.
+/// Example:
+/// ```rust,ignore
+/// struct App {
+///     clock: SubComponent<Clock, clock::Msg>,
+/// }
+///
+/// impl Component<Msg> for App {
+///     fn view(&self) -> Node<Msg> {
+///         div(vec![], vec![self.clock.view()])
+///     }
+///     ...
+/// }
+/// ```
+pub struct SubComponent<APP, MSG>
+where
+    MSG: 'static,
+{
+    component_id: u32,
+    program: Program<APP, MSG>,
+}
+
+impl<APP, MSG> SubComponent<APP, MSG>
+where
+    MSG: 'static,
+    APP: Component<MSG> + 'static,
+{
+    /// Instantiate the sub component, its view is rendered into a detached element
+    /// and moved into the placeholder once the placeholder is created in the DOM.
+    pub fn new(app: APP) -> Self {
+        let container = crate::document()
+            .create_element("div")
+            .expect("Unable to create element");
+        let program = Program::new_append_to_mount(app, &container);
+        let component_id = create_unique_identifier();
+        let program_clone = program.clone();
+        let mount: Mount = Rc::new(move |element: &Element| {
+            element
+                .append_child(&program_clone.root_node())
+                .expect("Unable to mount sub component");
+        });
+        MOUNT_POINTS.with(|mount_points| {
+            mount_points.borrow_mut().insert(component_id, mount)
+        });
+        SubComponent {
+            component_id,
+            program,
+        }
+    }
+
+    /// The placeholder element where the sub component is mounted.
+    /// This is placed in the view of the parent component.
+    pub fn view<PMSG>(&self) -> Node<PMSG> {
+        crate::html::div(
+            vec![attr(DATA_SAURON_COMPONENT, self.component_id)],
+            vec![],
+        )
+    }
+
+    /// dispatch a msg to the sub component, this only updates the view of the sub component
+    pub fn dispatch(&self, msg: MSG) {
+        self.program.dispatch(msg)
+    }
+
+    /// access the state of the sub component
+    pub fn with_app<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&APP) -> R,
+    {
+        f(&self.program.app.borrow())
+    }
+}
+
+impl<APP, MSG> Drop for SubComponent<APP, MSG>
+where
+    MSG: 'static,
+{
+    fn drop(&mut self) {
+        let component_id = self.component_id;
+        MOUNT_POINTS.with(|mount_points| {
+            mount_points.borrow_mut().remove(&component_id)
+        });
+    }
+}
@@ -0,0 +1,246 @@
+//! Manages the event listeners, intervals and animation frame loops
+//! that are requested by the `subscriptions` of a component.
+//!
+//! The subscriptions are re-evaluated after every update, the existing
+//! listeners whose key is still present are kept and only have their callback swapped,
+//! the ones that are no longer present are removed and their closures are dropped.
+use crate::{
+    mt_dom::Callback,
+    sub::{SubKey, Subscription},
+    Dispatch, Event, Sub,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::EventTarget;
+
+/// The subscriptions that are currently active in the browser
+pub(crate) struct ActiveSubscriptions<MSG> {
+    active: Vec<(SubKey, ActiveSubscription<MSG>)>,
+}
+
+/// The animation frame closure, which is taken out when the subscription is removed
+type FrameClosure = Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>>;
+
+/// A live subscription, the closures are removed from the browser when this is dropped
+enum ActiveSubscription<MSG> {
+    Listener {
+        target: EventTarget,
+        event_name: &'static str,
+        callback: Rc<RefCell<Callback<Event, Option<MSG>>>>,
+        closure: Closure<dyn FnMut(web_sys::Event)>,
+    },
+    Interval {
+        handle: i32,
+        callback: Rc<RefCell<Callback<(), MSG>>>,
+        // kept alive for as long as the interval is active
+        _closure: Closure<dyn FnMut()>,
+    },
+    AnimationFrame {
+        handle: Rc<Cell<i32>>,
+        callback: Rc<RefCell<Callback<f64, MSG>>>,
+        // the closure requests the next frame with itself,
+        // so it has to be taken out to break the cycle
+        closure: FrameClosure,
+    },
+}
+
+impl<MSG> ActiveSubscriptions<MSG>
+where
+    MSG: 'static,
+{
+    pub(crate) fn new() -> Self {
+        ActiveSubscriptions { active: vec![] }
+    }
+
+    /// Make the active subscriptions match `sub`.
+    /// Subscriptions with the same key are matched in the order they appear.
+    pub(crate) fn update<DSP>(&mut self, program: &DSP, sub: Sub<MSG>)
+    where
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let mut previous: Vec<Option<(SubKey, ActiveSubscription<MSG>)>> =
+            self.active.drain(..).map(Some).collect();
+        for subscription in sub.subscriptions {
+            let key = subscription.key();
+            let existing = previous
+                .iter_mut()
+                .find(|active| matches!(active, Some((k, _)) if *k == key))
+                .and_then(Option::take);
+            let active = match existing {
+                Some((_, active)) => active.with_callback(subscription),
+                None => ActiveSubscription::create(program, subscription),
+            };
+            self.active.push((key, active));
+        }
+        // the remaining previous subscriptions are dropped here
+    }
+}
+
+impl<MSG> ActiveSubscription<MSG>
+where
+    MSG: 'static,
+{
+    fn create<DSP>(program: &DSP, subscription: Subscription<MSG>) -> Self
+    where
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        match subscription {
+            Subscription::Window(event_name, cb) => {
+                let target: EventTarget = crate::window().unchecked_into();
+                Self::create_listener(program, target, event_name, cb)
+            }
+            Subscription::Document(event_name, cb) => {
+                let target: EventTarget = crate::document().unchecked_into();
+                Self::create_listener(program, target, event_name, cb)
+            }
+            Subscription::Interval(millis, cb) => {
+                let callback = Rc::new(RefCell::new(cb));
+                let callback_clone = Rc::clone(&callback);
+                let program = program.clone();
+                let closure: Closure<dyn FnMut()> =
+                    Closure::wrap(Box::new(move || {
+                        let msg = callback_clone.borrow().emit(());
+                        program.dispatch(msg);
+                    }));
+                let handle = crate::window()
+                    .set_interval_with_callback_and_timeout_and_arguments_0(
+                        closure.as_ref().unchecked_ref(),
+                        millis,
+                    )
+                    .expect("Unable to start interval");
+                ActiveSubscription::Interval {
+                    handle,
+                    callback,
+                    _closure: closure,
+                }
+            }
+            Subscription::AnimationFrame(cb) => {
+                let callback = Rc::new(RefCell::new(cb));
+                let handle = Rc::new(Cell::new(0));
+                let closure: FrameClosure = Rc::new(RefCell::new(None));
+
+                let callback_clone = Rc::clone(&callback);
+                let handle_clone = Rc::clone(&handle);
+                let closure_clone = Rc::clone(&closure);
+                let program = program.clone();
+                *closure.borrow_mut() =
+                    Some(Closure::wrap(Box::new(move |timestamp: f64| {
+                        let msg = callback_clone.borrow().emit(timestamp);
+                        program.dispatch(msg);
+                        // the closure is gone when the subscription was removed
+                        if let Some(closure) = closure_clone.borrow().as_ref() {
+                            handle_clone.set(request_frame(closure));
+                        }
+                    })));
+                handle.set(request_frame(
+                    closure.borrow().as_ref().expect("must have a closure"),
+                ));
+                ActiveSubscription::AnimationFrame {
+                    handle,
+                    callback,
+                    closure,
+                }
+            }
+        }
+    }
+
+    fn create_listener<DSP>(
+        program: &DSP,
+        target: EventTarget,
+        event_name: &'static str,
+        cb: Callback<Event, Option<MSG>>,
+    ) -> Self
+    where
+        DSP: Dispatch<MSG> + Clone + 'static,
+    {
+        let callback = Rc::new(RefCell::new(cb));
+        let callback_clone = Rc::clone(&callback);
+        let program = program.clone();
+        let closure: Closure<dyn FnMut(web_sys::Event)> =
+            Closure::wrap(Box::new(move |event: web_sys::Event| {
+                let msg = callback_clone.borrow().emit(event);
+                if let Some(msg) = msg {
+                    program.dispatch(msg);
+                }
+            }));
+        target
+            .add_event_listener_with_callback(
+                event_name,
+                closure.as_ref().unchecked_ref(),
+            )
+            .expect("Unable to attach event listener");
+        ActiveSubscription::Listener {
+            target,
+            event_name,
+            callback,
+            closure,
+        }
+    }
+
+    /// replace the callback of this active subscription with the one in `subscription`,
+    /// which has the same key, so the listener doesn't have to be re-attached
+    fn with_callback(self, subscription: Subscription<MSG>) -> Self {
+        match (&self, subscription) {
+            (
+                ActiveSubscription::Listener { callback, .. },
+                Subscription::Window(_, cb),
+            )
+            | (
+                ActiveSubscription::Listener { callback, .. },
+                Subscription::Document(_, cb),
+            ) => *callback.borrow_mut() = cb,
+            (
+                ActiveSubscription::Interval { callback, .. },
+                Subscription::Interval(_, cb),
+            ) => *callback.borrow_mut() = cb,
+            (
+                ActiveSubscription::AnimationFrame { callback, .. },
+                Subscription::AnimationFrame(cb),
+            ) => *callback.borrow_mut() = cb,
+            _ => unreachable!("subscriptions with the same key"),
+        }
+        self
+    }
+}
+
+impl<MSG> Drop for ActiveSubscription<MSG> {
+    fn drop(&mut self) {
+        match self {
+            ActiveSubscription::Listener {
+                target,
+                event_name,
+                closure,
+                ..
+            } => {
+                target
+                    .remove_event_listener_with_callback(
+                        event_name,
+                        closure.as_ref().unchecked_ref(),
+                    )
+                    .expect("Unable to remove event listener");
+            }
+            ActiveSubscription::Interval { handle, .. } => {
+                crate::window().clear_interval_with_handle(*handle);
+            }
+            ActiveSubscription::AnimationFrame {
+                handle, closure, ..
+            } => {
+                crate::window()
+                    .cancel_animation_frame(handle.get())
+                    .expect("Unable to cancel animation frame");
+                // this also stops the loop when the subscription is removed
+                // from within the dispatch of the current frame
+                closure.borrow_mut().take();
+            }
+        }
+    }
+}
+
+fn request_frame(closure: &Closure<dyn FnMut(f64)>) -> i32 {
+    crate::window()
+        .request_animation_frame(closure.as_ref().unchecked_ref())
+        .expect("Unable to request animation frame")
+}
@@ -0,0 +1,202 @@
+use crate::{Cmd, Component};
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+
+/// provides Cmds which emit MSG after a timeout or on an interval,
+/// and Cmds which debounce or throttle a stream of MSG.
+#[derive(Copy, Clone, Debug)]
+pub struct Timer;
+
+/// A handle to a timer started from the `Timer` Cmds.
+/// The timer is cancelled when `cancel` is called or when the last clone of the handle is dropped,
+/// so the handle is usually stored in the model of the component.
+/// Starting another timer with the same handle cancels the previous one.
+#[derive(Clone, Default)]
+pub struct TimerHandle {
+    active: Rc<RefCell<Option<ActiveTimer>>>,
+}
+
+/// A timer which is running in the browser,
+/// the timer is cleared and the closure is dropped when this is dropped.
+struct ActiveTimer {
+    handle: i32,
+    is_interval: bool,
+    _closure: Closure<dyn FnMut()>,
+}
+
+impl Timer {
+    /// emit the MSG after `millis` milliseconds have passed
+    pub fn delay<F, APP, MSG>(millis: i32, f: F) -> Cmd<APP, MSG>
+    where
+        F: Fn() -> MSG + Clone + 'static,
+        MSG: 'static,
+        APP: Component<MSG> + 'static,
+    {
+        Cmd::new(move |program| {
+            let f = f.clone();
+            // the closure is freed by the browser after it is invoked
+            let closure = Closure::once_into_js(move || {
+                program.dispatch(f());
+            });
+            crate::window()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(
+                    closure.unchecked_ref(),
+                    millis,
+                )
+                .expect("Unable to start timeout");
+        })
+    }
+
+    /// emit the MSG every `millis` milliseconds until the `handle` is cancelled or dropped
+    pub fn interval<F, APP, MSG>(
+        handle: &TimerHandle,
+        millis: i32,
+        f: F,
+    ) -> Cmd<APP, MSG>
+    where
+        F: Fn() -> MSG + Clone + 'static,
+        MSG: 'static,
+        APP: Component<MSG> + 'static,
+    {
+        let handle = handle.clone();
+        Cmd::new(move |program| {
+            let f = f.clone();
+            handle.start_interval(millis, move || program.dispatch(f()));
+        })
+    }
+
+    /// emit the MSG only after `millis` milliseconds have passed without
+    /// another debounced MSG using the same `handle`.
+    ///
+    /// Example:
+    /// ```rust,ignore
+    /// Msg::ChangeSearch(search) => {
+    ///     Timer::debounce(&self.search_timer, 300, Msg::Search(search))
+    /// }
+    /// ```
+    pub fn debounce<APP, MSG>(
+        handle: &TimerHandle,
+        millis: i32,
+        msg: MSG,
+    ) -> Cmd<APP, MSG>
+    where
+        MSG: Clone + 'static,
+        APP: Component<MSG> + 'static,
+    {
+        let handle = handle.clone();
+        Cmd::new(move |program| {
+            let msg = msg.clone();
+            handle.start_timeout(millis, move || program.dispatch(msg));
+        })
+    }
+
+    /// emit the MSG right away, then ignore the throttled MSG using the same `handle`
+    /// until `millis` milliseconds have passed.
+    pub fn throttle<APP, MSG>(
+        handle: &TimerHandle,
+        millis: i32,
+        msg: MSG,
+    ) -> Cmd<APP, MSG>
+    where
+        MSG: Clone + 'static,
+        APP: Component<MSG> + 'static,
+    {
+        let handle = handle.clone();
+        Cmd::new(move |program| {
+            if !handle.is_active() {
+                handle.start_timeout(millis, || ());
+                program.dispatch(msg.clone());
+            }
+        })
+    }
+}
+
+impl TimerHandle {
+    /// create a handle with no running timer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// returns true if there is a timer running in this handle
+    pub fn is_active(&self) -> bool {
+        self.active.borrow().is_some()
+    }
+
+    /// stop the running timer, the pending MSG will not be emitted
+    pub fn cancel(&self) {
+        // dropping the active timer clears it
+        self.active.borrow_mut().take();
+    }
+
+    /// execute `f` once after `millis`, the handle becomes inactive after that
+    pub(crate) fn start_timeout<F>(&self, millis: i32, f: F)
+    where
+        F: FnOnce() + 'static,
+    {
+        // a weak reference, so the pending closure doesn't keep the handle alive
+        let active: Weak<RefCell<Option<ActiveTimer>>> =
+            Rc::downgrade(&self.active);
+        let mut f = Some(f);
+        let closure: Closure<dyn FnMut()> =
+            Closure::wrap(Box::new(move || {
+                let f = f.take();
+                // the closure is only freed by the browser after this call returns
+                if let Some(active) = active.upgrade() {
+                    active.borrow_mut().take();
+                }
+                if let Some(f) = f {
+                    f();
+                }
+            }));
+        let handle = crate::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                closure.as_ref().unchecked_ref(),
+                millis,
+            )
+            .expect("Unable to start timeout");
+        self.set_active(ActiveTimer {
+            handle,
+            is_interval: false,
+            _closure: closure,
+        });
+    }
+
+    /// execute `f` every `millis` until the handle is cancelled
+    fn start_interval<F>(&self, millis: i32, f: F)
+    where
+        F: Fn() + 'static,
+    {
+        let closure: Closure<dyn FnMut()> = Closure::wrap(Box::new(f));
+        let handle = crate::window()
+            .set_interval_with_callback_and_timeout_and_arguments_0(
+                closure.as_ref().unchecked_ref(),
+                millis,
+            )
+            .expect("Unable to start interval");
+        self.set_active(ActiveTimer {
+            handle,
+            is_interval: true,
+            _closure: closure,
+        });
+    }
+
+    fn set_active(&self, timer: ActiveTimer) {
+        // take the previous timer out first, so it is dropped
+        // after the borrow is released
+        let previous = self.active.borrow_mut().replace(timer);
+        drop(previous);
+    }
+}
+
+impl Drop for ActiveTimer {
+    fn drop(&mut self) {
+        if self.is_interval {
+            crate::window().clear_interval_with_handle(self.handle);
+        } else {
+            crate::window().clear_timeout_with_handle(self.handle);
+        }
+    }
+}
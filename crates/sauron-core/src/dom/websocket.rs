@@ -0,0 +1,395 @@
+//! provides functions for connecting to a server with WebSocket
+use crate::{dom::TimerHandle, Cmd, Component, Dispatch};
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{BinaryType, CloseEvent, MessageEvent};
+
+/// Provides Cmds for opening a WebSocket connection, sending frames into it and closing it.
+/// The connection is owned by a `SocketHandle` which is usually stored in the model.
+///
+/// Example:
+/// ```rust,ignore
+/// fn init(&self) -> Cmd<Self, Msg> {
+///     WebSocket::open_with_reconnect(
+///         &self.socket,
+///         "ws://127.0.0.1:3030/echo",
+///         Reconnect::default(),
+///         Msg::SocketEvent,
+///     )
+/// }
+///
+/// fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+///     match msg {
+///         Msg::Send(text) => WebSocket::send(&self.socket, Frame::Text(text)),
+///         Msg::SocketEvent(SocketEvent::Text(text)) => { ... }
+///         ...
+///     }
+/// }
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct WebSocket;
+
+/// A frame which is sent or received in the WebSocket connection
+#[derive(Debug, Clone, PartialEq)]
+pub enum Frame {
+    /// a text frame
+    Text(String),
+    /// a binary frame
+    Binary(Vec<u8>),
+}
+
+/// The events of the WebSocket connection, which are emitted as MSG
+#[derive(Debug, Clone, PartialEq)]
+pub enum SocketEvent {
+    /// the connection is established
+    Open,
+    /// a text frame is received
+    Text(String),
+    /// a binary frame is received
+    Binary(Vec<u8>),
+    /// the connection is closed by the server or the network
+    Close {
+        /// the close code sent by the server
+        code: u16,
+        /// the reason sent by the server
+        reason: String,
+        /// whether the connection was closed cleanly
+        was_clean: bool,
+    },
+    /// an error occured in the connection, the connection is closed afterwards
+    Error,
+    /// the connection will be opened again after `delay` milliseconds
+    Reconnecting {
+        /// the number of the reconnection attempt, starting at 1
+        attempt: u32,
+        /// the delay before reconnecting in milliseconds
+        delay: i32,
+    },
+}
+
+/// How the connection is re-opened after it is closed by the server or the network.
+/// The delay is doubled on every failed attempt, up to `max_delay`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reconnect {
+    /// the delay before the first attempt in milliseconds
+    pub initial_delay: i32,
+    /// the maximum delay between attempts in milliseconds
+    pub max_delay: i32,
+    /// stop reconnecting after this number of failed attempts, None to retry forever
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for Reconnect {
+    fn default() -> Self {
+        Reconnect {
+            initial_delay: 500,
+            max_delay: 30_000,
+            max_attempts: None,
+        }
+    }
+}
+
+impl Reconnect {
+    /// the delay before the reconnection `attempt`, None if there are no more attempts
+    pub fn delay(&self, attempt: u32) -> Option<i32> {
+        if let Some(max_attempts) = self.max_attempts {
+            if attempt > max_attempts {
+                return None;
+            }
+        }
+        let factor = 2_i32.saturating_pow(attempt.saturating_sub(1));
+        Some(
+            self.initial_delay
+                .saturating_mul(factor)
+                .min(self.max_delay),
+        )
+    }
+}
+
+/// A handle to the WebSocket connection opened with `WebSocket::open`.
+/// The connection is closed when `WebSocket::close` is executed
+/// or when another connection is opened with the same handle.
+#[derive(Clone, Default)]
+pub struct SocketHandle {
+    state: Rc<RefCell<SocketState>>,
+}
+
+#[derive(Default)]
+struct SocketState {
+    socket: Option<ActiveSocket>,
+    /// the frames which are sent before the connection is open
+    pending: Vec<Frame>,
+    /// the number of failed reconnection attempts since the last open
+    attempt: u32,
+    reconnect_timer: TimerHandle,
+}
+
+/// The browser WebSocket and its event handlers,
+/// the handlers are detached and the connection is closed when this is dropped.
+struct ActiveSocket {
+    socket: web_sys::WebSocket,
+    _on_open: Closure<dyn FnMut(JsValue)>,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_close: Closure<dyn FnMut(CloseEvent)>,
+    _on_error: Closure<dyn FnMut(JsValue)>,
+}
+
+impl WebSocket {
+    /// open a connection to the `url`, the events of the connection are emitted
+    /// as the MSG created from `f`. Any previous connection of the handle is closed.
+    pub fn open<F, APP, MSG>(
+        handle: &SocketHandle,
+        url: &str,
+        f: F,
+    ) -> Cmd<APP, MSG>
+    where
+        F: Fn(SocketEvent) -> MSG + 'static,
+        APP: Component<MSG> + 'static,
+        MSG: 'static,
+    {
+        Self::open_with_options(handle, url, None, f)
+    }
+
+    /// open a connection to the `url`, which is opened again using `reconnect`
+    /// when it is closed by the server or the network.
+    pub fn open_with_reconnect<F, APP, MSG>(
+        handle: &SocketHandle,
+        url: &str,
+        reconnect: Reconnect,
+        f: F,
+    ) -> Cmd<APP, MSG>
+    where
+        F: Fn(SocketEvent) -> MSG + 'static,
+        APP: Component<MSG> + 'static,
+        MSG: 'static,
+    {
+        Self::open_with_options(handle, url, Some(reconnect), f)
+    }
+
+    fn open_with_options<F, APP, MSG>(
+        handle: &SocketHandle,
+        url: &str,
+        reconnect: Option<Reconnect>,
+        f: F,
+    ) -> Cmd<APP, MSG>
+    where
+        F: Fn(SocketEvent) -> MSG + 'static,
+        APP: Component<MSG> + 'static,
+        MSG: 'static,
+    {
+        let handle = handle.clone();
+        let url = url.to_string();
+        let f = Rc::new(f);
+        Cmd::new(move |program| {
+            let f = Rc::clone(&f);
+            // the socket is owned by the app, so the program is weakly referenced
+            // to not keep the app and the socket alive after they are dropped
+            let program = program.downgrade().unwrap_or(program);
+            let emit: Rc<dyn Fn(SocketEvent)> =
+                Rc::new(move |event| program.dispatch(f(event)));
+            handle.close();
+            connect(&handle.state, &url, reconnect, emit);
+        })
+    }
+
+    /// send the frame into the connection,
+    /// the frame is queued when the connection is not open yet
+    pub fn send<APP, MSG>(handle: &SocketHandle, frame: Frame) -> Cmd<APP, MSG>
+    where
+        APP: Component<MSG> + 'static,
+        MSG: 'static,
+    {
+        let handle = handle.clone();
+        Cmd::new(move |_program| handle.send(frame.clone()))
+    }
+
+    /// close the connection, it will not be reconnected
+    /// and no more events are emitted from it
+    pub fn close<APP, MSG>(handle: &SocketHandle) -> Cmd<APP, MSG>
+    where
+        APP: Component<MSG> + 'static,
+        MSG: 'static,
+    {
+        let handle = handle.clone();
+        Cmd::new(move |_program| handle.close())
+    }
+}
+
+impl SocketHandle {
+    /// create a handle with no connection
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// returns true if the connection is open
+    pub fn is_open(&self) -> bool {
+        match &self.state.borrow().socket {
+            Some(active) => {
+                active.socket.ready_state() == web_sys::WebSocket::OPEN
+            }
+            None => false,
+        }
+    }
+
+    fn send(&self, frame: Frame) {
+        if self.is_open() {
+            let state = self.state.borrow();
+            let active = state.socket.as_ref().expect("must have a socket");
+            send_frame(&active.socket, &frame);
+        } else {
+            self.state.borrow_mut().pending.push(frame);
+        }
+    }
+
+    fn close(&self) {
+        // take the socket out first, so it is dropped after the borrow is released
+        let socket = {
+            let mut state = self.state.borrow_mut();
+            state.reconnect_timer.cancel();
+            state.pending.clear();
+            state.attempt = 0;
+            state.socket.take()
+        };
+        drop(socket);
+    }
+}
+
+/// create the browser WebSocket and attach the event handlers
+fn connect(
+    state: &Rc<RefCell<SocketState>>,
+    url: &str,
+    reconnect: Option<Reconnect>,
+    emit: Rc<dyn Fn(SocketEvent)>,
+) {
+    let socket = match web_sys::WebSocket::new(url) {
+        Ok(socket) => socket,
+        Err(e) => {
+            log::error!("Unable to open WebSocket to {}: {:?}", url, e);
+            emit(SocketEvent::Error);
+            return;
+        }
+    };
+    socket.set_binary_type(BinaryType::Arraybuffer);
+
+    // the handlers only have a weak reference to the state,
+    // so the connection is closed when the handle is dropped
+    let weak_state: Weak<RefCell<SocketState>> = Rc::downgrade(state);
+
+    let on_open: Closure<dyn FnMut(JsValue)> = {
+        let weak_state = weak_state.clone();
+        let emit = Rc::clone(&emit);
+        Closure::wrap(Box::new(move |_| {
+            if let Some(state) = weak_state.upgrade() {
+                let (socket, pending) = {
+                    let mut state = state.borrow_mut();
+                    state.attempt = 0;
+                    let pending = std::mem::take(&mut state.pending);
+                    let socket = state
+                        .socket
+                        .as_ref()
+                        .map(|active| active.socket.clone());
+                    (socket, pending)
+                };
+                if let Some(socket) = socket {
+                    for frame in pending.iter() {
+                        send_frame(&socket, frame);
+                    }
+                }
+            }
+            emit(SocketEvent::Open);
+        }))
+    };
+
+    let on_message: Closure<dyn FnMut(MessageEvent)> = {
+        let emit = Rc::clone(&emit);
+        Closure::wrap(Box::new(move |event: MessageEvent| {
+            let data = event.data();
+            if let Some(text) = data.as_string() {
+                emit(SocketEvent::Text(text));
+            } else if data.is_instance_of::<js_sys::ArrayBuffer>() {
+                emit(SocketEvent::Binary(
+                    js_sys::Uint8Array::new(&data).to_vec(),
+                ));
+            } else {
+                log::warn!("Unsupported WebSocket message: {:?}", data);
+            }
+        }))
+    };
+
+    let on_close: Closure<dyn FnMut(CloseEvent)> = {
+        let weak_state = weak_state.clone();
+        let url = url.to_string();
+        let emit = Rc::clone(&emit);
+        Closure::wrap(Box::new(move |event: CloseEvent| {
+            emit(SocketEvent::Close {
+                code: event.code(),
+                reason: event.reason(),
+                was_clean: event.was_clean(),
+            });
+            let (reconnect, state) = match (reconnect, weak_state.upgrade()) {
+                (Some(reconnect), Some(state)) => (reconnect, state),
+                _ => return,
+            };
+            let attempt = {
+                let mut state = state.borrow_mut();
+                state.attempt += 1;
+                state.attempt
+            };
+            if let Some(delay) = reconnect.delay(attempt) {
+                emit(SocketEvent::Reconnecting { attempt, delay });
+                let weak_state = weak_state.clone();
+                let url = url.clone();
+                let emit = Rc::clone(&emit);
+                let timer = state.borrow().reconnect_timer.clone();
+                timer.start_timeout(delay, move || {
+                    if let Some(state) = weak_state.upgrade() {
+                        connect(&state, &url, Some(reconnect), emit);
+                    }
+                });
+            }
+        }))
+    };
+
+    let on_error: Closure<dyn FnMut(JsValue)> = {
+        let emit = Rc::clone(&emit);
+        Closure::wrap(Box::new(move |_| emit(SocketEvent::Error)))
+    };
+
+    socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+    socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+    socket.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+    let previous = state.borrow_mut().socket.replace(ActiveSocket {
+        socket,
+        _on_open: on_open,
+        _on_message: on_message,
+        _on_close: on_close,
+        _on_error: on_error,
+    });
+    drop(previous);
+}
+
+fn send_frame(socket: &web_sys::WebSocket, frame: &Frame) {
+    let result = match frame {
+        Frame::Text(text) => socket.send_with_str(text),
+        Frame::Binary(bytes) => socket.send_with_u8_array(bytes),
+    };
+    if let Err(e) = result {
+        log::error!("Unable to send WebSocket frame: {:?}", e);
+    }
+}
+
+impl Drop for ActiveSocket {
+    fn drop(&mut self) {
+        self.socket.set_onopen(None);
+        self.socket.set_onmessage(None);
+        self.socket.set_onclose(None);
+        self.socket.set_onerror(None);
+        // closing an already closed socket does nothing
+        let _ = self.socket.close();
+    }
+}
@@ -1,5 +1,7 @@
 use crate::{
-    dom::created_node::create_closure_wrap, Attribute, Cmd, Component,
+    dom::created_node::{add_event_listener, create_closure_wrap},
+    html::events::{event_filters, event_options},
+    Attribute, Cmd, Component,
 };
 use std::fmt::Debug;
 use wasm_bindgen::{self, prelude::*, JsCast};
@@ -10,7 +12,9 @@ use web_sys::EventTarget;
 pub struct Window;
 
 impl Window {
-    /// attach an event listender to the window
+    /// attach an event listender to the window.
+    /// The listeners can not be removed, use `Sub::on_window` in the
+    /// component subscriptions for listeners that can be turned off.
     pub fn add_event_listeners<APP, MSG>(
         event_listeners: Vec<Attribute<MSG>>,
     ) -> Cmd<APP, MSG>
@@ -26,19 +30,19 @@ impl Window {
 
             for event_attr in event_listeners.iter() {
                 let event_str = event_attr.name();
-                for event_cb in event_attr.value() {
-                    let callback =
-                        event_cb.get_callback().expect("expecting a callback");
-
+                let options = event_options(event_attr);
+                let filters = event_filters(event_attr);
+                for callback in event_attr.get_callback() {
                     let closure_wrap: Closure<dyn FnMut(web_sys::Event)> =
-                        create_closure_wrap(&program, &callback);
-                    window
-                        .add_event_listener_with_callback(
-                            event_str,
-                            closure_wrap.as_ref().unchecked_ref(),
-                        )
-                        .expect("Unable to attached event listener");
-
+                        create_closure_wrap(
+                            &program, callback, &options, &filters,
+                        );
+                    add_event_listener(
+                        window,
+                        event_str,
+                        &closure_wrap,
+                        &options,
+                    );
                     closure_wrap.forget();
                 }
             }
@@ -0,0 +1,95 @@
+//! A file which is chosen by the user in a file input, or dropped into an element.
+//!
+//! With the `with-dom` feature this wraps the browser `web_sys::File`,
+//! without it the content of the file is kept in memory so the listeners
+//! which receive the files can be called in the tests.
+use cfg_if::cfg_if;
+
+/// The metadata of a file chosen by the user
+#[derive(Debug, Clone, PartialEq)]
+pub struct File {
+    name: String,
+    mime_type: String,
+    size: u64,
+    last_modified: f64,
+    #[cfg(feature = "with-dom")]
+    file: web_sys::File,
+    #[cfg(not(feature = "with-dom"))]
+    content: Vec<u8>,
+}
+
+impl File {
+    /// the name of the file, without the path
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// the mime type of the file, ie: `image/png`,
+    /// this is empty when the type can not be determined
+    pub fn mime_type(&self) -> &str {
+        &self.mime_type
+    }
+
+    /// the size of the file in bytes
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// the time the file is last modified, in milliseconds since the unix epoch
+    pub fn last_modified(&self) -> f64 {
+        self.last_modified
+    }
+}
+
+cfg_if! {if #[cfg(feature = "with-dom")] {
+    impl File {
+        /// the browser file
+        pub fn as_web_sys(&self) -> &web_sys::File {
+            &self.file
+        }
+
+        /// the files in the file list, such as the files of an input element
+        pub fn from_file_list(files: &web_sys::FileList) -> Vec<File> {
+            (0..files.length())
+                .filter_map(|i| files.get(i))
+                .map(File::from)
+                .collect()
+        }
+    }
+
+    impl From<web_sys::File> for File {
+        fn from(file: web_sys::File) -> Self {
+            File {
+                name: file.name(),
+                mime_type: file.type_(),
+                size: file.size() as u64,
+                last_modified: file.last_modified(),
+                file,
+            }
+        }
+    }
+} else {
+    impl File {
+        /// a file with the content, which is used in place of the file chosen by the user
+        pub fn new(name: &str, mime_type: &str, content: Vec<u8>) -> Self {
+            File {
+                name: name.to_string(),
+                mime_type: mime_type.to_string(),
+                size: content.len() as u64,
+                last_modified: 0.0,
+                content,
+            }
+        }
+
+        /// set the time the file is last modified, in milliseconds since the unix epoch
+        pub fn with_last_modified(mut self, last_modified: f64) -> Self {
+            self.last_modified = last_modified;
+            self
+        }
+
+        /// the content of the file
+        pub fn content(&self) -> &[u8] {
+            &self.content
+        }
+    }
+}}
@@ -4,12 +4,10 @@ pub use mt_dom::{element, element_ns, text};
 
 #[macro_use]
 pub mod attributes;
+pub mod events;
 pub mod tags;
 pub mod units;
 
-#[cfg(feature = "with-dom")]
-pub use crate::dom::events;
-
 pub use tags::commons::*;
 
 /// A help function which render the view when the condition is met, otherwise
@@ -2,7 +2,7 @@
 //!
 use crate::Attribute;
 pub use attribute_macros::*;
-pub use attribute_value::AttributeValue;
+pub use attribute_value::{AttributeValue, EventFilter};
 pub use style::Style;
 pub use style_macro::*;
 pub use value::Value;
@@ -149,6 +149,85 @@ where
     attributes
 }
 
+/// merge the values of the attribute with this name into the string which is set in the element.
+/// The `class` values are joined with a space, the `style` values are concatenated
+/// and only the last value is used for the other attributes.
+pub(crate) fn merge_plain_values(
+    name: &str,
+    values: &[&AttributeValue],
+) -> Option<String> {
+    let mut strings = values.iter().filter_map(|value| match value {
+        AttributeValue::Simple(simple) => Some(simple.to_string()),
+        AttributeValue::Style(styles) => {
+            Some(styles.iter().map(|style| format!("{};", style)).collect())
+        }
+        AttributeValue::FunctionCall(value) => Some(value.to_string()),
+        AttributeValue::Empty
+        | AttributeValue::EventOptions(_)
+        | AttributeValue::EventFilter(_) => None,
+    });
+    match name {
+        "class" => {
+            let classes: Vec<String> = strings.collect();
+            if classes.is_empty() {
+                None
+            } else {
+                Some(classes.join(" "))
+            }
+        }
+        "style" => strings.fold(None, |merged: Option<String>, style| {
+            Some(match merged {
+                Some(merged) if merged.is_empty() || merged.ends_with(';') => {
+                    merged + &style
+                }
+                Some(merged) => merged + ";" + &style,
+                None => style,
+            })
+        }),
+        _ => strings.next_back(),
+    }
+}
+
+/// These are the html attributes which are enabled by their presence,
+/// regardless of their value
+pub const HTML_BOOLEAN_ATTRS: [&str; 24] = [
+    "allowfullscreen",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "defer",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "ismap",
+    "itemscope",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "playsinline",
+    "readonly",
+    "required",
+    "reversed",
+    "selected",
+];
+
+/// returns true if the boolean attribute with these values is enabled,
+/// it is disabled when it has no value or the last value is `false`
+pub(crate) fn is_boolean_attr_enabled(values: &[&AttributeValue]) -> bool {
+    match values.last() {
+        Some(AttributeValue::Simple(Value::Bool(value))) => *value,
+        Some(AttributeValue::Simple(value)) => value.as_str() != Some("false"),
+        Some(AttributeValue::Empty) | None => false,
+        Some(_) => true,
+    }
+}
+
 /// set the checked value, used checkbox and radio buttons
 pub fn checked<MSG>(is_checked: bool) -> Attribute<MSG> {
     if is_checked {
@@ -1,4 +1,9 @@
-use crate::prelude::{Style, Value};
+use crate::{
+    html::events::EventOptions,
+    prelude::{Style, Value},
+    Event,
+};
+use std::{fmt, rc::Rc};
 
 /// Values of an attribute can be in these variants
 #[derive(Debug, Clone)]
@@ -11,6 +16,10 @@ pub enum AttributeValue {
     Style(Vec<Style>),
     /// no value
     Empty,
+    /// the options of the event listeners in the same attribute
+    EventOptions(EventOptions),
+    /// the filter of the events of the listeners in the same attribute
+    EventFilter(EventFilter),
 }
 
 impl PartialEq for AttributeValue {
@@ -23,11 +32,40 @@ impl PartialEq for AttributeValue {
             (AttributeValue::Style(this), AttributeValue::Style(other)) => {
                 this == other
             }
+            (
+                AttributeValue::EventOptions(this),
+                AttributeValue::EventOptions(other),
+            ) => this == other,
             _ => true,
         }
     }
 }
 
+/// Decides whether the listeners are called for the event
+#[derive(Clone)]
+pub struct EventFilter(Rc<dyn Fn(&Event) -> bool>);
+
+impl EventFilter {
+    /// create a filter, the listeners are only called when `f` returns true
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&Event) -> bool + 'static,
+    {
+        EventFilter(Rc::new(f))
+    }
+
+    /// returns true if the listeners are called for the event
+    pub fn accepts(&self, event: &Event) -> bool {
+        (self.0)(event)
+    }
+}
+
+impl fmt::Debug for EventFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "EventFilter")
+    }
+}
+
 impl AttributeValue {
     /// create an attribute from Vec<Style>
     pub fn from_styles(styles: Vec<Style>) -> Self {
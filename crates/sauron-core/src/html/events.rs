@@ -0,0 +1,514 @@
+//! https://developer.mozilla.org/en-US/docs/Web/Events
+//!
+//! Without the `with-dom` feature the events are the synthetic events
+//! which can be created in the tests, see [`SyntheticEvent`](enum.SyntheticEvent.html)
+
+use crate::{
+    file::File,
+    html::attributes::{AttributeValue, EventFilter},
+    Attribute, Callback, Event,
+};
+use cfg_if::cfg_if;
+use std::{cell::RefCell, rc::Rc};
+
+cfg_if! {if #[cfg(feature = "with-dom")] {
+    use wasm_bindgen::JsCast;
+    pub use web_sys::{
+        AnimationEvent, ClipboardEvent, CompositionEvent, DataTransfer, DragEvent,
+        FocusEvent, HashChangeEvent, KeyboardEvent, MouseEvent, PointerEvent, Touch,
+        TouchEvent, TouchList, TransitionEvent, WheelEvent,
+    };
+    use wasm_bindgen::JsValue;
+    use web_sys::{
+        EventTarget, HtmlElement, HtmlInputElement, HtmlOptionElement, HtmlSelectElement,
+        HtmlTextAreaElement,
+    };
+} else {
+    mod synthetic;
+    pub use synthetic::{
+        AnimationEvent, ClipboardEvent, CompositionEvent, DataTransfer, DragEvent,
+        FocusEvent, HashChangeEvent, KeyboardEvent, MouseEvent, PointerEvent,
+        SyntheticEvent, Touch, TouchEvent, TouchList, TransitionEvent, WheelEvent,
+    };
+    use synthetic::{
+        to_animation_event, to_clipboard_event, to_composition_event, to_drag_event,
+        to_focus_event, to_hashchange_event, to_input_event, to_keyboard_event,
+        to_mouse_event, to_pointer_event, to_scroll_offset, to_touch_event,
+        to_transition_event, to_wheel_event,
+    };
+}}
+
+/// an event builder
+pub fn on<F, MSG>(event_name: &'static str, f: F) -> Attribute<MSG>
+where
+    F: Fn(Event) -> MSG + 'static,
+{
+    mt_dom::on(event_name, Callback::from(f))
+}
+
+/// an event builder with the options of the event listener
+pub fn on_with_options<F, MSG>(
+    event_name: &'static str,
+    options: EventOptions,
+    f: F,
+) -> Attribute<MSG>
+where
+    F: Fn(Event) -> MSG + 'static,
+{
+    on(event_name, f).with_options(options)
+}
+
+/// an event builder which only emits the MSG when `f` returns Some,
+/// the options such as `prevent_default` are not applied to the events which are filtered out.
+/// The filter applies to the other listeners of `event_name` in the same element too.
+pub fn on_filter_map<F, MSG>(event_name: &'static str, f: F) -> Attribute<MSG>
+where
+    F: Fn(Event) -> Option<MSG> + 'static,
+    MSG: 'static,
+{
+    // the msg is produced by the filter and taken by the callback which is called right after it
+    let msg = Rc::new(RefCell::new(None));
+    let msg_clone = Rc::clone(&msg);
+    let filter = EventFilter::new(move |event| {
+        let filtered = f(event.clone());
+        let accepted = filtered.is_some();
+        *msg_clone.borrow_mut() = filtered;
+        accepted
+    });
+    let listener = on(event_name, move |_| {
+        msg.borrow_mut()
+            .take()
+            .expect("the filter must be called before the listener")
+    });
+    let filter_attr =
+        mt_dom::attr(event_name, AttributeValue::EventFilter(filter));
+    mt_dom::merge_attributes_of_same_name(&[&listener, &filter_attr])
+        .pop()
+        .expect("must have the merged attribute")
+}
+
+/// the filters of the event listeners in the attribute,
+/// the listeners are only called when all of the filters accept the event
+pub(crate) fn event_filters<MSG>(attr: &Attribute<MSG>) -> Vec<EventFilter> {
+    attr.get_plain()
+        .into_iter()
+        .filter_map(|value| match value {
+            AttributeValue::EventFilter(filter) => Some(filter.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The options of an event listener
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EventOptions {
+    /// call `prevent_default` on the event before calling the listener
+    pub prevent_default: bool,
+    /// call `stop_propagation` on the event before calling the listener,
+    /// so the listeners of the containing elements are not called
+    pub stop_propagation: bool,
+    /// the listener will not call `prevent_default`, so the browser
+    /// can scroll right away without waiting for the listener
+    pub passive: bool,
+    /// call the listener in the capture phase, before the listeners of the elements inside it
+    pub capture: bool,
+    /// remove the listener after it is called once
+    pub once: bool,
+}
+
+#[cfg(feature = "with-dom")]
+impl EventOptions {
+    /// the options which are set in either of the two
+    fn merge(self, other: Self) -> Self {
+        EventOptions {
+            prevent_default: self.prevent_default || other.prevent_default,
+            stop_propagation: self.stop_propagation || other.stop_propagation,
+            passive: self.passive || other.passive,
+            capture: self.capture || other.capture,
+            once: self.once || other.once,
+        }
+    }
+}
+
+/// the options of the event listeners in the attribute.
+/// The attributes with the same name in an element are merged,
+/// so their listeners share the same options.
+#[cfg(feature = "with-dom")]
+pub(crate) fn event_options<MSG>(attr: &Attribute<MSG>) -> EventOptions {
+    attr.get_plain()
+        .into_iter()
+        .filter_map(|value| match value {
+            AttributeValue::EventOptions(options) => Some(*options),
+            _ => None,
+        })
+        .fold(EventOptions::default(), EventOptions::merge)
+}
+
+/// Set the options of the event listeners, ie: `on_click(..).prevent_default()`
+pub trait ListenerOptions: Sized {
+    /// add the options to the event listener
+    fn with_options(self, options: EventOptions) -> Self;
+
+    /// call `prevent_default` on the event before calling the listener
+    fn prevent_default(self) -> Self {
+        self.with_options(EventOptions {
+            prevent_default: true,
+            ..Default::default()
+        })
+    }
+
+    /// call `stop_propagation` on the event before calling the listener
+    fn stop_propagation(self) -> Self {
+        self.with_options(EventOptions {
+            stop_propagation: true,
+            ..Default::default()
+        })
+    }
+
+    /// mark the listener as passive
+    fn passive(self) -> Self {
+        self.with_options(EventOptions {
+            passive: true,
+            ..Default::default()
+        })
+    }
+
+    /// call the listener in the capture phase
+    fn capture(self) -> Self {
+        self.with_options(EventOptions {
+            capture: true,
+            ..Default::default()
+        })
+    }
+
+    /// remove the listener after it is called once
+    fn once(self) -> Self {
+        self.with_options(EventOptions {
+            once: true,
+            ..Default::default()
+        })
+    }
+}
+
+impl<MSG> ListenerOptions for Attribute<MSG> {
+    fn with_options(self, options: EventOptions) -> Self {
+        let options_attr =
+            mt_dom::attr(*self.name(), AttributeValue::EventOptions(options));
+        mt_dom::merge_attributes_of_same_name(&[&self, &options_attr])
+            .pop()
+            .expect("must have the merged attribute")
+    }
+}
+
+/// on click event
+pub fn on_click<F, MSG>(f: F) -> Attribute<MSG>
+where
+    F: Fn(MouseEvent) -> MSG + 'static,
+{
+    on("click", move |event: Event| f(to_mouse_event(event)))
+}
+/// attach callback to the drop event with the files which are dropped into the element.
+/// The default action of the browser, which is opening the file, is prevented.
+/// The element also needs `on_dragover(..).prevent_default()` to accept the drop.
+pub fn on_drop_files<F, MSG>(f: F) -> Attribute<MSG>
+where
+    F: Fn(Vec<File>) -> MSG + 'static,
+    MSG: 'static,
+{
+    on("drop", move |event: Event| {
+        f(dropped_files(&to_drag_event(event)))
+    })
+    .prevent_default()
+}
+
+/// the files which are dropped in the drop event
+pub fn dropped_files(event: &DragEvent) -> Vec<File> {
+    cfg_if! {if #[cfg(feature = "with-dom")] {
+        event
+            .data_transfer()
+            .and_then(|data_transfer| data_transfer.files())
+            .map(|files| File::from_file_list(&files))
+            .unwrap_or_default()
+    } else {
+        event
+            .data_transfer()
+            .map(|data_transfer| data_transfer.files())
+            .unwrap_or_default()
+    }}
+}
+
+/// attach callback to the scroll event
+pub fn on_scroll<F, MSG>(f: F) -> Attribute<MSG>
+where
+    F: Fn((i32, i32)) -> MSG + 'static,
+    MSG: 'static,
+{
+    on("scroll", move |event: Event| f(to_scroll_offset(event)))
+}
+
+macro_rules! declare_events {
+
+    ( $(
+         $(#[$attr:meta])*
+         $name:ident => $event:ident => $mapper:ident => $ret:ty;
+       )*
+     ) => {
+        $(
+            doc_comment!{
+                concat!("attach an [",stringify!($name),"](https://developer.mozilla.org/en-US/docs/Web/API/GlobalEventHandlers/",stringify!($name),") event to the html element"),
+                $(#[$attr])*
+                #[inline]
+                pub fn $name<CB, MSG>(cb: CB) -> crate::Attribute<MSG>
+                    where CB: Fn($ret) -> MSG + 'static,
+                          MSG: 'static,
+                    {
+                        on(stringify!($event), move|event:Event|{
+                            cb($mapper(event))
+                        })
+                }
+            }
+         )*
+    }
+}
+
+macro_rules! declare_html_events{
+    ( $(
+         $(#[$attr:meta])*
+         $name:ident => $event:ident => $mapper:ident => $ret:ty;
+       )*
+     ) => {
+        declare_events!{ $($name => $event => $mapper => $ret;)* }
+
+        /// html events
+        pub const HTML_EVENTS: [&'static str; 58] = [$(stringify!($event),)*];
+    }
+}
+
+fn as_is(event: Event) -> Event {
+    event
+}
+
+/// a custom InputEvent to contain the values of the form control
+/// which received the input and change events
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InputEvent {
+    /// the input value, this is the text content of contenteditable elements
+    pub value: String,
+    /// the checked state of the checkbox and radio input
+    pub checked: bool,
+    /// the values of the selected options of the select element
+    pub selected: Vec<String>,
+    /// the files chosen in the file input
+    pub files: Vec<File>,
+}
+
+impl InputEvent {
+    /// create an InputEvent with the value of the input element
+    pub fn new(value: String) -> Self {
+        InputEvent {
+            value,
+            ..Default::default()
+        }
+    }
+
+    /// set the checked state of the checkbox and radio input
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    /// set the values of the selected options,
+    /// the value is set to the first selected option
+    pub fn with_selected(mut self, selected: Vec<String>) -> Self {
+        self.value = selected.first().cloned().unwrap_or_default();
+        self.selected = selected;
+        self
+    }
+
+    /// set the files chosen in the file input
+    pub fn with_files(mut self, files: Vec<File>) -> Self {
+        self.files = files;
+        self
+    }
+}
+
+cfg_if! {if #[cfg(feature = "with-dom")] {
+    /// the scroll top and scroll left of the event target
+    fn to_scroll_offset(event: Event) -> (i32, i32) {
+        let target = event.target().expect("can't get target");
+        let element: &web_sys::Element =
+            target.dyn_ref().expect("Cant cast to Element");
+        (element.scroll_top(), element.scroll_left())
+    }
+
+    /// convert a generic event to MouseEvent
+    fn to_mouse_event(event: Event) -> MouseEvent {
+        event.dyn_into().expect("Unable to cast to mouse event")
+    }
+
+    fn to_wheel_event(event: Event) -> WheelEvent {
+        event.dyn_into().expect("unable to cast to wheel event")
+    }
+
+    fn to_keyboard_event(event: Event) -> KeyboardEvent {
+        event.dyn_into().expect("unable to cast to keyboard event")
+    }
+
+    fn to_pointer_event(event: Event) -> PointerEvent {
+        event.dyn_into().expect("unable to cast to pointer event")
+    }
+
+    fn to_touch_event(event: Event) -> TouchEvent {
+        event.dyn_into().expect("unable to cast to touch event")
+    }
+
+    fn to_drag_event(event: Event) -> DragEvent {
+        event.dyn_into().expect("unable to cast to drag event")
+    }
+
+    fn to_focus_event(event: Event) -> FocusEvent {
+        event.dyn_into().expect("unable to cast to focus event")
+    }
+
+    fn to_clipboard_event(event: Event) -> ClipboardEvent {
+        event.dyn_into().expect("unable to cast to clipboard event")
+    }
+
+    fn to_composition_event(event: Event) -> CompositionEvent {
+        event
+            .dyn_into()
+            .expect("unable to cast to composition event")
+    }
+
+    fn to_animation_event(event: Event) -> AnimationEvent {
+        event.dyn_into().expect("unable to cast to animation event")
+    }
+
+    fn to_transition_event(event: Event) -> TransitionEvent {
+        event
+            .dyn_into()
+            .expect("unable to cast to transition event")
+    }
+
+    fn to_hashchange_event(event: Event) -> HashChangeEvent {
+        event
+            .dyn_into()
+            .expect("unable to cast to hashchange event")
+    }
+
+    /// the values of the form control which is the target of the event,
+    /// the values of the custom elements are read from their `value` and `checked` properties
+    fn to_input_event(event: Event) -> InputEvent {
+        let target: EventTarget = match event.target() {
+            Some(target) => target,
+            None => return InputEvent::default(),
+        };
+        if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
+            InputEvent::new(input.value())
+                .with_checked(input.checked())
+                .with_files(
+                    input
+                        .files()
+                        .map(|files| File::from_file_list(&files))
+                        .unwrap_or_default(),
+                )
+        } else if let Some(textarea) = target.dyn_ref::<HtmlTextAreaElement>() {
+            InputEvent::new(textarea.value())
+        } else if let Some(select) = target.dyn_ref::<HtmlSelectElement>() {
+            let options = select.selected_options();
+            let selected = (0..options.length())
+                .filter_map(|i| options.item(i))
+                .filter_map(|option| {
+                    option
+                        .dyn_ref::<HtmlOptionElement>()
+                        .map(|option| option.value())
+                })
+                .collect();
+            InputEvent {
+                value: select.value(),
+                selected,
+                ..Default::default()
+            }
+        } else if let Some(element) = target
+            .dyn_ref::<HtmlElement>()
+            .filter(|element| element.is_content_editable())
+        {
+            InputEvent::new(element.text_content().unwrap_or_default())
+        } else {
+            let property = |name: &str| {
+                js_sys::Reflect::get(&target, &JsValue::from_str(name))
+                    .unwrap_or(JsValue::UNDEFINED)
+            };
+            InputEvent::new(property("value").as_string().unwrap_or_default())
+                .with_checked(property("checked").as_bool().unwrap_or(false))
+        }
+    }
+}}
+
+declare_html_events! {
+    // Mouse events
+    on_auxclick => auxclick => to_mouse_event => MouseEvent;
+    on_animationend => animationend => to_animation_event => AnimationEvent;
+    on_transitionend => transitionend => to_transition_event => TransitionEvent;
+    on_contextmenu => contextmenu => to_mouse_event => MouseEvent;
+    on_dblclick  => dblclick => to_mouse_event => MouseEvent;
+    on_mousedown => mousedown => to_mouse_event => MouseEvent;
+    on_mouseenter => mouseenter => to_mouse_event => MouseEvent;
+    on_mouseleave => mouseleave => to_mouse_event => MouseEvent;
+    on_mousemove => mousemove => to_mouse_event => MouseEvent;
+    on_mouseover => mouseover => to_mouse_event => MouseEvent;
+    on_mouseout => mouseout => to_mouse_event => MouseEvent;
+    on_mouseup => mouseup => to_mouse_event => MouseEvent;
+    on_pointerlockchange => pointerlockchange => as_is => Event;
+    on_pointerlockerror => pointerlockerror => as_is => Event;
+    on_select => select => as_is => Event;
+    on_wheel => wheel => to_wheel_event => WheelEvent;
+    on_doubleclick => dblclick => to_mouse_event => MouseEvent;
+    on_keydown => keydown => to_keyboard_event => KeyboardEvent;
+    on_keypress => keypress => to_keyboard_event => KeyboardEvent;
+    on_keyup => keyup => to_keyboard_event => KeyboardEvent;
+    on_focus => focus => to_focus_event => FocusEvent;
+    on_blur => blur => to_focus_event => FocusEvent;
+    on_reset => reset => as_is => Event;
+    on_submit => submit => as_is => Event;
+    on_input => input => to_input_event => InputEvent;
+    on_change => change => to_input_event => InputEvent;
+    on_broadcast => broadcast => as_is => Event;
+    on_hashchange => hashchange => to_hashchange_event => HashChangeEvent;
+    on_readystatechange => readystatechange => as_is => Event;
+    // Pointer events
+    on_pointerdown => pointerdown => to_pointer_event => PointerEvent;
+    on_pointerup => pointerup => to_pointer_event => PointerEvent;
+    on_pointermove => pointermove => to_pointer_event => PointerEvent;
+    on_pointerover => pointerover => to_pointer_event => PointerEvent;
+    on_pointerout => pointerout => to_pointer_event => PointerEvent;
+    on_pointerenter => pointerenter => to_pointer_event => PointerEvent;
+    on_pointerleave => pointerleave => to_pointer_event => PointerEvent;
+    on_pointercancel => pointercancel => to_pointer_event => PointerEvent;
+    on_gotpointercapture => gotpointercapture => to_pointer_event => PointerEvent;
+    on_lostpointercapture => lostpointercapture => to_pointer_event => PointerEvent;
+    // Touch events
+    on_touchstart => touchstart => to_touch_event => TouchEvent;
+    on_touchmove => touchmove => to_touch_event => TouchEvent;
+    on_touchend => touchend => to_touch_event => TouchEvent;
+    on_touchcancel => touchcancel => to_touch_event => TouchEvent;
+    // Drag and drop events
+    on_drag => drag => to_drag_event => DragEvent;
+    on_dragstart => dragstart => to_drag_event => DragEvent;
+    on_dragend => dragend => to_drag_event => DragEvent;
+    on_dragenter => dragenter => to_drag_event => DragEvent;
+    on_dragleave => dragleave => to_drag_event => DragEvent;
+    on_dragover => dragover => to_drag_event => DragEvent;
+    on_drop => drop => to_drag_event => DragEvent;
+    // Focus events which bubble
+    on_focusin => focusin => to_focus_event => FocusEvent;
+    on_focusout => focusout => to_focus_event => FocusEvent;
+    // Clipboard events
+    on_copy => copy => to_clipboard_event => ClipboardEvent;
+    on_cut => cut => to_clipboard_event => ClipboardEvent;
+    on_paste => paste => to_clipboard_event => ClipboardEvent;
+    // Composition events of the input method editors
+    on_compositionstart => compositionstart => to_composition_event => CompositionEvent;
+    on_compositionupdate => compositionupdate => to_composition_event => CompositionEvent;
+    on_compositionend => compositionend => to_composition_event => CompositionEvent;
+}
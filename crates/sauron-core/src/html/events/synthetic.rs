@@ -0,0 +1,879 @@
+//! Synthetic events which are used in place of the browser events
+//! when sauron is used without the `with-dom` feature.
+//! They carry the same fields which the event mappers read from the browser events,
+//! so the event listeners in the view can be called in a plain `cargo test`.
+//!
+//! Example:
+//! ```rust
+//! use sauron_core::{
+//!     html::{attributes::*, events::*, *},
+//!     test_harness::trigger,
+//!     Node,
+//! };
+//!
+//! let view: Node<(i32, i32)> = div(
+//!     vec![],
+//!     vec![button(
+//!         vec![id("add"), on_click(|event| (event.client_x(), event.client_y()))],
+//!         vec![text("+")],
+//!     )],
+//! );
+//! assert_eq!((10, 20), trigger(&view, "#add", MouseEvent::new(10, 20)));
+//! ```
+use super::InputEvent;
+use crate::file::File;
+use std::{cell::RefCell, convert::Infallible, rc::Rc};
+
+/// An event created off-DOM, this is the `Event` type without the `with-dom` feature
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntheticEvent {
+    /// an event which carries no data such as submit and reset
+    Plain,
+    /// click, mousedown, mousemove and the other mouse events
+    Mouse(MouseEvent),
+    /// wheel
+    Wheel(WheelEvent),
+    /// keydown, keypress and keyup
+    Keyboard(KeyboardEvent),
+    /// input and change, carrying the value of the input element
+    Input(InputEvent),
+    /// scroll, carrying the scroll top and scroll left of the element
+    Scroll(i32, i32),
+    /// animationend
+    Animation(AnimationEvent),
+    /// transitionend
+    Transition(TransitionEvent),
+    /// hashchange
+    HashChange(HashChangeEvent),
+    /// pointerdown, pointermove, pointerup and the other pointer events
+    Pointer(PointerEvent),
+    /// touchstart, touchmove, touchend and touchcancel
+    Touch(TouchEvent),
+    /// dragstart, dragover, drop and the other drag and drop events
+    Drag(DragEvent),
+    /// focus, blur, focusin and focusout
+    Focus(FocusEvent),
+    /// copy, cut and paste
+    Clipboard(ClipboardEvent),
+    /// compositionstart, compositionupdate and compositionend
+    Composition(CompositionEvent),
+}
+
+impl SyntheticEvent {
+    /// does nothing, there is no default action off-DOM
+    pub fn prevent_default(&self) {}
+
+    /// does nothing, the synthetic events are not propagated
+    pub fn stop_propagation(&self) {}
+}
+
+impl Default for SyntheticEvent {
+    fn default() -> Self {
+        SyntheticEvent::Plain
+    }
+}
+
+impl From<InputEvent> for SyntheticEvent {
+    fn from(event: InputEvent) -> Self {
+        SyntheticEvent::Input(event)
+    }
+}
+
+/// the state of the modifier keys when the event happened
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Modifiers {
+    alt: bool,
+    ctrl: bool,
+    shift: bool,
+    meta: bool,
+}
+
+/// implement the builders and getters of the modifier keys
+macro_rules! impl_modifiers {
+    ($($event:ident),*) => {
+        $(
+            impl $event {
+                /// the alt key is pressed
+                pub fn with_alt(mut self) -> Self {
+                    self.modifiers.alt = true;
+                    self
+                }
+
+                /// the ctrl key is pressed
+                pub fn with_ctrl(mut self) -> Self {
+                    self.modifiers.ctrl = true;
+                    self
+                }
+
+                /// the shift key is pressed
+                pub fn with_shift(mut self) -> Self {
+                    self.modifiers.shift = true;
+                    self
+                }
+
+                /// the meta key is pressed
+                pub fn with_meta(mut self) -> Self {
+                    self.modifiers.meta = true;
+                    self
+                }
+
+                /// returns true if the alt key is pressed
+                pub fn alt_key(&self) -> bool {
+                    self.modifiers.alt
+                }
+
+                /// returns true if the ctrl key is pressed
+                pub fn ctrl_key(&self) -> bool {
+                    self.modifiers.ctrl
+                }
+
+                /// returns true if the shift key is pressed
+                pub fn shift_key(&self) -> bool {
+                    self.modifiers.shift
+                }
+
+                /// returns true if the meta key is pressed
+                pub fn meta_key(&self) -> bool {
+                    self.modifiers.meta
+                }
+            }
+        )*
+    };
+}
+
+/// implement the conversion of each event into the SyntheticEvent variant
+macro_rules! impl_from_event {
+    ($($event:ident => $variant:ident),*) => {
+        $(
+            impl From<$event> for SyntheticEvent {
+                fn from(event: $event) -> Self {
+                    SyntheticEvent::$variant(event)
+                }
+            }
+        )*
+    };
+}
+
+/// A synthetic mouse event
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MouseEvent {
+    client_x: i32,
+    client_y: i32,
+    button: i16,
+    modifiers: Modifiers,
+}
+
+impl MouseEvent {
+    /// a mouse event at this position relative to the viewport,
+    /// with the main button pressed
+    pub fn new(client_x: i32, client_y: i32) -> Self {
+        MouseEvent {
+            client_x,
+            client_y,
+            ..Default::default()
+        }
+    }
+
+    /// the button which is pressed, 0 is the main button, 2 is the secondary button
+    pub fn with_button(mut self, button: i16) -> Self {
+        self.button = button;
+        self
+    }
+
+    /// the horizontal position relative to the viewport
+    pub fn client_x(&self) -> i32 {
+        self.client_x
+    }
+
+    /// the vertical position relative to the viewport
+    pub fn client_y(&self) -> i32 {
+        self.client_y
+    }
+
+    /// alias of client_x
+    pub fn x(&self) -> i32 {
+        self.client_x
+    }
+
+    /// alias of client_y
+    pub fn y(&self) -> i32 {
+        self.client_y
+    }
+
+    /// the button which is pressed
+    pub fn button(&self) -> i16 {
+        self.button
+    }
+}
+
+/// A synthetic keyboard event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeyboardEvent {
+    key: String,
+    code: String,
+    key_code: u32,
+    repeat: bool,
+    modifiers: Modifiers,
+}
+
+impl KeyboardEvent {
+    /// a keyboard event for the key value, ie: `Enter`, `Escape`, `a`
+    pub fn new(key: &str) -> Self {
+        KeyboardEvent {
+            key: key.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// the physical key code, ie: `KeyA`, `Enter`
+    pub fn with_code(mut self, code: &str) -> Self {
+        self.code = code.to_string();
+        self
+    }
+
+    /// the legacy numeric key code, ie: 13 for enter
+    pub fn with_key_code(mut self, key_code: u32) -> Self {
+        self.key_code = key_code;
+        self
+    }
+
+    /// the key is held down and is repeating
+    pub fn with_repeat(mut self) -> Self {
+        self.repeat = true;
+        self
+    }
+
+    /// the key value
+    pub fn key(&self) -> String {
+        self.key.clone()
+    }
+
+    /// the physical key code
+    pub fn code(&self) -> String {
+        self.code.clone()
+    }
+
+    /// the legacy numeric key code
+    pub fn key_code(&self) -> u32 {
+        self.key_code
+    }
+
+    /// returns true if the key is repeating
+    pub fn repeat(&self) -> bool {
+        self.repeat
+    }
+}
+
+/// A synthetic pointer event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PointerEvent {
+    client_x: i32,
+    client_y: i32,
+    button: i16,
+    pointer_id: i32,
+    pointer_type: String,
+    pressure: f32,
+    is_primary: bool,
+    modifiers: Modifiers,
+}
+
+impl PointerEvent {
+    /// the primary mouse pointer at this position relative to the viewport
+    pub fn new(client_x: i32, client_y: i32) -> Self {
+        PointerEvent {
+            client_x,
+            client_y,
+            pointer_type: "mouse".to_string(),
+            is_primary: true,
+            ..Default::default()
+        }
+    }
+
+    /// the button which is pressed, 0 is the main button, 2 is the secondary button
+    pub fn with_button(mut self, button: i16) -> Self {
+        self.button = button;
+        self
+    }
+
+    /// the unique id of the pointer, which is different for each touch point
+    pub fn with_pointer_id(mut self, pointer_id: i32) -> Self {
+        self.pointer_id = pointer_id;
+        self
+    }
+
+    /// the kind of device, ie: `mouse`, `pen`, `touch`
+    pub fn with_pointer_type(mut self, pointer_type: &str) -> Self {
+        self.pointer_type = pointer_type.to_string();
+        self
+    }
+
+    /// the pressure of the pointer from 0.0 to 1.0
+    pub fn with_pressure(mut self, pressure: f32) -> Self {
+        self.pressure = pressure;
+        self
+    }
+
+    /// the pointer is not the primary pointer, such as the second finger in a touch
+    pub fn with_secondary(mut self) -> Self {
+        self.is_primary = false;
+        self
+    }
+
+    /// the horizontal position relative to the viewport
+    pub fn client_x(&self) -> i32 {
+        self.client_x
+    }
+
+    /// the vertical position relative to the viewport
+    pub fn client_y(&self) -> i32 {
+        self.client_y
+    }
+
+    /// alias of client_x
+    pub fn x(&self) -> i32 {
+        self.client_x
+    }
+
+    /// alias of client_y
+    pub fn y(&self) -> i32 {
+        self.client_y
+    }
+
+    /// the button which is pressed
+    pub fn button(&self) -> i16 {
+        self.button
+    }
+
+    /// the unique id of the pointer
+    pub fn pointer_id(&self) -> i32 {
+        self.pointer_id
+    }
+
+    /// the kind of device
+    pub fn pointer_type(&self) -> String {
+        self.pointer_type.clone()
+    }
+
+    /// the pressure of the pointer
+    pub fn pressure(&self) -> f32 {
+        self.pressure
+    }
+
+    /// returns true if it is the primary pointer
+    pub fn is_primary(&self) -> bool {
+        self.is_primary
+    }
+}
+
+/// A synthetic wheel event
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WheelEvent {
+    client_x: i32,
+    client_y: i32,
+    delta_x: f64,
+    delta_y: f64,
+    delta_z: f64,
+    delta_mode: u32,
+    modifiers: Modifiers,
+}
+
+impl WheelEvent {
+    /// the deltas are in pixels
+    pub const DOM_DELTA_PIXEL: u32 = 0;
+    /// the deltas are in lines
+    pub const DOM_DELTA_LINE: u32 = 1;
+    /// the deltas are in pages
+    pub const DOM_DELTA_PAGE: u32 = 2;
+
+    /// a wheel event scrolling by these pixels, at the top left of the viewport
+    pub fn new(delta_x: f64, delta_y: f64) -> Self {
+        WheelEvent {
+            delta_x,
+            delta_y,
+            ..Default::default()
+        }
+    }
+
+    /// the position of the pointer relative to the viewport
+    pub fn with_position(mut self, client_x: i32, client_y: i32) -> Self {
+        self.client_x = client_x;
+        self.client_y = client_y;
+        self
+    }
+
+    /// the scroll amount along the z-axis
+    pub fn with_delta_z(mut self, delta_z: f64) -> Self {
+        self.delta_z = delta_z;
+        self
+    }
+
+    /// the unit of the deltas, one of the `DOM_DELTA_*` constants
+    pub fn with_delta_mode(mut self, delta_mode: u32) -> Self {
+        self.delta_mode = delta_mode;
+        self
+    }
+
+    /// the horizontal position relative to the viewport
+    pub fn client_x(&self) -> i32 {
+        self.client_x
+    }
+
+    /// the vertical position relative to the viewport
+    pub fn client_y(&self) -> i32 {
+        self.client_y
+    }
+
+    /// the horizontal scroll amount
+    pub fn delta_x(&self) -> f64 {
+        self.delta_x
+    }
+
+    /// the vertical scroll amount
+    pub fn delta_y(&self) -> f64 {
+        self.delta_y
+    }
+
+    /// the scroll amount along the z-axis
+    pub fn delta_z(&self) -> f64 {
+        self.delta_z
+    }
+
+    /// the unit of the deltas
+    pub fn delta_mode(&self) -> u32 {
+        self.delta_mode
+    }
+}
+
+/// A synthetic touch point
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Touch {
+    identifier: i32,
+    client_x: i32,
+    client_y: i32,
+}
+
+impl Touch {
+    /// the touch point with this identifier at the position relative to the viewport
+    pub fn new(identifier: i32, client_x: i32, client_y: i32) -> Self {
+        Touch {
+            identifier,
+            client_x,
+            client_y,
+        }
+    }
+
+    /// the unique id of the touch point for as long as it touches the surface
+    pub fn identifier(&self) -> i32 {
+        self.identifier
+    }
+
+    /// the horizontal position relative to the viewport
+    pub fn client_x(&self) -> i32 {
+        self.client_x
+    }
+
+    /// the vertical position relative to the viewport
+    pub fn client_y(&self) -> i32 {
+        self.client_y
+    }
+}
+
+/// A list of synthetic touch points
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TouchList(Vec<Touch>);
+
+impl TouchList {
+    /// the number of touch points
+    pub fn length(&self) -> u32 {
+        self.0.len() as u32
+    }
+
+    /// the touch point at this index
+    pub fn get(&self, index: u32) -> Option<Touch> {
+        self.0.get(index as usize).copied()
+    }
+
+    /// alias of get
+    pub fn item(&self, index: u32) -> Option<Touch> {
+        self.get(index)
+    }
+}
+
+/// A synthetic touch event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TouchEvent {
+    touches: Vec<Touch>,
+    changed_touches: Option<Vec<Touch>>,
+    modifiers: Modifiers,
+}
+
+impl TouchEvent {
+    /// a touch event with these points touching the surface
+    pub fn new(touches: Vec<Touch>) -> Self {
+        TouchEvent {
+            touches,
+            ..Default::default()
+        }
+    }
+
+    /// the touch points which changed in this event,
+    /// these are the same as the touches when not set
+    pub fn with_changed_touches(mut self, changed_touches: Vec<Touch>) -> Self {
+        self.changed_touches = Some(changed_touches);
+        self
+    }
+
+    /// all the points touching the surface
+    pub fn touches(&self) -> TouchList {
+        TouchList(self.touches.clone())
+    }
+
+    /// the points touching the surface which started in the target element,
+    /// this is the same as the touches off-DOM
+    pub fn target_touches(&self) -> TouchList {
+        self.touches()
+    }
+
+    /// the touch points which changed in this event
+    pub fn changed_touches(&self) -> TouchList {
+        TouchList(
+            self.changed_touches
+                .clone()
+                .unwrap_or_else(|| self.touches.clone()),
+        )
+    }
+}
+
+impl_modifiers!(
+    MouseEvent,
+    WheelEvent,
+    KeyboardEvent,
+    PointerEvent,
+    TouchEvent
+);
+
+/// The data being dragged or copied in the synthetic drag and clipboard events.
+/// The clones share the same data, so the data set in a listener can be read by the test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataTransfer {
+    data: Rc<RefCell<Vec<(String, String)>>>,
+    drop_effect: Rc<RefCell<String>>,
+    files: Vec<File>,
+}
+
+impl DataTransfer {
+    /// an empty data transfer
+    pub fn new() -> Self {
+        DataTransfer {
+            data: Rc::new(RefCell::new(vec![])),
+            drop_effect: Rc::new(RefCell::new("none".to_string())),
+            files: vec![],
+        }
+    }
+
+    /// the data transfer with the files being dropped
+    pub fn with_files(mut self, files: Vec<File>) -> Self {
+        self.files = files;
+        self
+    }
+
+    /// the files being dropped
+    pub fn files(&self) -> Vec<File> {
+        self.files.clone()
+    }
+
+    /// the data transfer with the data of this format, ie: `text/plain`
+    pub fn with_data(self, format: &str, data: &str) -> Self {
+        self.set_data(format, data).expect("must set data");
+        self
+    }
+
+    /// the data of the format, this is empty if there is no data of this format
+    pub fn get_data(&self, format: &str) -> Result<String, Infallible> {
+        Ok(self
+            .data
+            .borrow()
+            .iter()
+            .find(|(fmt, _)| fmt == format)
+            .map(|(_, data)| data.clone())
+            .unwrap_or_default())
+    }
+
+    /// set the data of the format, replacing the existing data of the format
+    pub fn set_data(&self, format: &str, data: &str) -> Result<(), Infallible> {
+        let mut all_data = self.data.borrow_mut();
+        all_data.retain(|(fmt, _)| fmt != format);
+        all_data.push((format.to_string(), data.to_string()));
+        Ok(())
+    }
+
+    /// the formats of the data, in the order they are set
+    pub fn types(&self) -> Vec<String> {
+        self.data
+            .borrow()
+            .iter()
+            .map(|(format, _)| format.clone())
+            .collect()
+    }
+
+    /// the effect of the drop, ie: `copy`, `move`, `link`, `none`
+    pub fn drop_effect(&self) -> String {
+        self.drop_effect.borrow().clone()
+    }
+
+    /// set the effect of the drop
+    pub fn set_drop_effect(&self, drop_effect: &str) {
+        *self.drop_effect.borrow_mut() = drop_effect.to_string();
+    }
+}
+
+impl Default for DataTransfer {
+    fn default() -> Self {
+        DataTransfer::new()
+    }
+}
+
+/// A synthetic drag and drop event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DragEvent {
+    client_x: i32,
+    client_y: i32,
+    data_transfer: Option<DataTransfer>,
+}
+
+impl DragEvent {
+    /// a drag event at this position relative to the viewport, carrying the data transfer
+    pub fn new(
+        client_x: i32,
+        client_y: i32,
+        data_transfer: DataTransfer,
+    ) -> Self {
+        DragEvent {
+            client_x,
+            client_y,
+            data_transfer: Some(data_transfer),
+        }
+    }
+
+    /// the horizontal position relative to the viewport
+    pub fn client_x(&self) -> i32 {
+        self.client_x
+    }
+
+    /// the vertical position relative to the viewport
+    pub fn client_y(&self) -> i32 {
+        self.client_y
+    }
+
+    /// the data being dragged
+    pub fn data_transfer(&self) -> Option<DataTransfer> {
+        self.data_transfer.clone()
+    }
+}
+
+/// A synthetic focus event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FocusEvent {
+    related_target: Option<String>,
+}
+
+impl FocusEvent {
+    /// a focus event without a related target
+    pub fn new() -> Self {
+        FocusEvent::default()
+    }
+
+    /// the id of the element losing the focus in focus and focusin,
+    /// or the element receiving the focus in blur and focusout
+    pub fn with_related_target(mut self, id: &str) -> Self {
+        self.related_target = Some(id.to_string());
+        self
+    }
+
+    /// the id of the related target, since there are no elements off-DOM
+    pub fn related_target(&self) -> Option<String> {
+        self.related_target.clone()
+    }
+}
+
+/// A synthetic copy, cut and paste event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClipboardEvent {
+    clipboard_data: Option<DataTransfer>,
+}
+
+impl ClipboardEvent {
+    /// a clipboard event carrying the data being pasted,
+    /// or the data transfer where the copied data is set
+    pub fn new(clipboard_data: DataTransfer) -> Self {
+        ClipboardEvent {
+            clipboard_data: Some(clipboard_data),
+        }
+    }
+
+    /// the data of the clipboard
+    pub fn clipboard_data(&self) -> Option<DataTransfer> {
+        self.clipboard_data.clone()
+    }
+}
+
+/// A synthetic composition event of the input method editors
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompositionEvent {
+    data: Option<String>,
+}
+
+impl CompositionEvent {
+    /// a composition event with the text being composed
+    pub fn new(data: &str) -> Self {
+        CompositionEvent {
+            data: Some(data.to_string()),
+        }
+    }
+
+    /// the text being composed
+    pub fn data(&self) -> Option<String> {
+        self.data.clone()
+    }
+}
+
+/// A synthetic animationend event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AnimationEvent {
+    animation_name: String,
+    elapsed_time: f32,
+}
+
+impl AnimationEvent {
+    /// the animation which ended after running for `elapsed_time` seconds
+    pub fn new(animation_name: &str, elapsed_time: f32) -> Self {
+        AnimationEvent {
+            animation_name: animation_name.to_string(),
+            elapsed_time,
+        }
+    }
+
+    /// the name of the animation
+    pub fn animation_name(&self) -> String {
+        self.animation_name.clone()
+    }
+
+    /// the time in seconds the animation has been running
+    pub fn elapsed_time(&self) -> f32 {
+        self.elapsed_time
+    }
+}
+
+/// A synthetic transitionend event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransitionEvent {
+    property_name: String,
+    elapsed_time: f32,
+}
+
+impl TransitionEvent {
+    /// the transition of the css property which ended after `elapsed_time` seconds
+    pub fn new(property_name: &str, elapsed_time: f32) -> Self {
+        TransitionEvent {
+            property_name: property_name.to_string(),
+            elapsed_time,
+        }
+    }
+
+    /// the name of the css property
+    pub fn property_name(&self) -> String {
+        self.property_name.clone()
+    }
+
+    /// the time in seconds the transition has been running
+    pub fn elapsed_time(&self) -> f32 {
+        self.elapsed_time
+    }
+}
+
+/// A synthetic hashchange event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HashChangeEvent {
+    old_url: String,
+    new_url: String,
+}
+
+impl HashChangeEvent {
+    /// the url changed from `old_url` to `new_url`
+    pub fn new(old_url: &str, new_url: &str) -> Self {
+        HashChangeEvent {
+            old_url: old_url.to_string(),
+            new_url: new_url.to_string(),
+        }
+    }
+
+    /// the url before the change
+    pub fn old_url(&self) -> String {
+        self.old_url.clone()
+    }
+
+    /// the url after the change
+    pub fn new_url(&self) -> String {
+        self.new_url.clone()
+    }
+}
+
+impl_from_event!(
+    MouseEvent => Mouse,
+    WheelEvent => Wheel,
+    KeyboardEvent => Keyboard,
+    AnimationEvent => Animation,
+    TransitionEvent => Transition,
+    HashChangeEvent => HashChange,
+    PointerEvent => Pointer,
+    TouchEvent => Touch,
+    DragEvent => Drag,
+    FocusEvent => Focus,
+    ClipboardEvent => Clipboard,
+    CompositionEvent => Composition
+);
+
+/// generate the mappers which take the event out of its variant,
+/// panics when it is a different kind of event the same way as
+/// casting a browser event to the wrong type
+macro_rules! declare_mappers {
+    ($($mapper:ident => $variant:ident => $ret:ty;)*) => {
+        $(
+            pub(super) fn $mapper(event: SyntheticEvent) -> $ret {
+                match event {
+                    SyntheticEvent::$variant(event) => event,
+                    _ => panic!(
+                        "unable to cast {:?} to {}",
+                        event,
+                        stringify!($ret)
+                    ),
+                }
+            }
+        )*
+    };
+}
+
+declare_mappers! {
+    to_mouse_event => Mouse => MouseEvent;
+    to_wheel_event => Wheel => WheelEvent;
+    to_keyboard_event => Keyboard => KeyboardEvent;
+    to_input_event => Input => InputEvent;
+    to_animation_event => Animation => AnimationEvent;
+    to_transition_event => Transition => TransitionEvent;
+    to_hashchange_event => HashChange => HashChangeEvent;
+    to_pointer_event => Pointer => PointerEvent;
+    to_touch_event => Touch => TouchEvent;
+    to_drag_event => Drag => DragEvent;
+    to_focus_event => Focus => FocusEvent;
+    to_clipboard_event => Clipboard => ClipboardEvent;
+    to_composition_event => Composition => CompositionEvent;
+}
+
+pub(super) fn to_scroll_offset(event: SyntheticEvent) -> (i32, i32) {
+    match event {
+        SyntheticEvent::Scroll(scroll_top, scroll_left) => {
+            (scroll_top, scroll_left)
+        }
+        _ => panic!("unable to cast {:?} to scroll event", event),
+    }
+}
@@ -209,3 +209,10 @@ declare_tags_and_macro_non_common! {
     title; // conflicts with html::attributes::title  , attributes::title   > tags::title
     slot;  // conflicts with html::attributes::slot   , attrributes::slot   > tags::slot
 }
+
+/// These are the html elements which can not have child nodes,
+/// they are written without the closing tag
+pub const HTML_VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta",
+    "param", "source", "track", "wbr",
+];
@@ -0,0 +1,77 @@
+//! Provides functions for embedding the initial state of the app into the rendered html.
+//! The state is serialized as json into a `<script type="application/json">` element,
+//! which is then read by the client side program so it starts from the same state as the server.
+//!
+use crate::{
+    html::{
+        attributes::{id, type_},
+        script, text,
+    },
+    Node, Render,
+};
+use serde::Serialize;
+use std::fmt;
+use thiserror::Error;
+
+/// The id of the script element which contains the serialized initial state
+pub const INITIAL_STATE_ID: &str = "sauron-initial-state";
+
+/// Errors that can occur when writing or reading the initial state
+#[derive(Debug, Error)]
+pub enum InitialStateError {
+    /// the state can not be serialized or deserialized into json
+    #[error("initial state json error: {0}")]
+    Json(#[from] serde_json::Error),
+    /// writing to the buffer failed
+    #[error("unable to write the initial state")]
+    Fmt(#[from] fmt::Error),
+    /// the script element containing the initial state is not found in the document
+    #[error("no initial state found in element with id: `{0}`")]
+    NotFound(&'static str),
+}
+
+/// create a script node which contains the json serialized state.
+/// The `<`, `>` and `&` characters are escaped into their unicode escape sequence
+/// so the content can not close the script element.
+pub fn initial_state_script<S, MSG>(
+    state: &S,
+) -> Result<Node<MSG>, InitialStateError>
+where
+    S: Serialize,
+{
+    let json = serde_json::to_string(state)?
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026");
+    Ok(script(
+        vec![type_("application/json"), id(INITIAL_STATE_ID)],
+        vec![text(json)],
+    ))
+}
+
+/// render the node followed by the script element containing the serialized state
+pub fn render_with_initial_state<S, MSG>(
+    node: &Node<MSG>,
+    state: &S,
+    buffer: &mut dyn fmt::Write,
+) -> Result<(), InitialStateError>
+where
+    S: Serialize,
+{
+    node.render(buffer)?;
+    initial_state_script::<S, MSG>(state)?.render(buffer)?;
+    Ok(())
+}
+
+/// read and deserialize the initial state which was embedded in the document
+#[cfg(feature = "with-dom")]
+pub fn read_initial_state<S>() -> Result<S, InitialStateError>
+where
+    S: serde::de::DeserializeOwned,
+{
+    let json = crate::document()
+        .get_element_by_id(INITIAL_STATE_ID)
+        .and_then(|script| script.text_content())
+        .ok_or(InitialStateError::NotFound(INITIAL_STATE_ID))?;
+    Ok(serde_json::from_str(&json)?)
+}
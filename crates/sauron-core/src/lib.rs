@@ -20,22 +20,39 @@ cfg_if! {if #[cfg(feature = "with-dom")] {
     pub type Event = web_sys::Event;
     pub use web_sys;
     pub use wasm_bindgen;
+} else {
+    /// When not in the browser, the events are synthetic events
+    /// which can be created in the tests
+    pub type Event = html::events::SyntheticEvent;
 }}
 
-/// When event is not needed, such as just rendering the dom
-/// tree in server side application
-#[cfg(not(feature = "with-dom"))]
-pub type Event = ();
-
 #[macro_use]
 pub mod html;
 #[macro_use]
 pub mod svg;
 #[macro_use]
 pub mod jss;
+pub mod cmd;
+mod component;
+mod dispatch;
+pub mod file;
+pub mod initial_state;
+pub mod query;
 mod render;
+pub mod router;
+pub mod snapshot;
+pub mod ssr;
+pub mod sub;
+pub mod test_harness;
 
-pub use render::Render;
+pub use cmd::Cmd;
+pub use component::Component;
+pub use dispatch::Dispatch;
+pub use file::File;
+pub use query::{QuerySelector, Selector, SelectorError};
+pub use render::{HtmlWriter, Render, RenderChunks, RenderOptions};
+pub use ssr::ServerProgram;
+pub use sub::Sub;
 
 use html::attributes::AttributeValue;
 use mt_dom::diff_with_key;
@@ -0,0 +1,498 @@
+//! Provides css selector queries over the virtual dom tree.
+//!
+//! The supported selectors are the tag, `*`, `#id`, `.class`, `[name]` and `[name=value]`,
+//! combined into compound selectors such as `li.item[data-id="1"]`,
+//! and the descendant (space) and child (`>`) combinators.
+//! The node which is queried is part of the tree being matched.
+//!
+//! Example:
+//! ```rust
+//! use sauron_core::{
+//!     html::{attributes::*, *},
+//!     Node, QuerySelector,
+//! };
+//!
+//! let mut view: Node<()> = table(
+//!     vec![],
+//!     vec![tr(vec![], vec![td(vec![], vec![text("1")]), td(vec![], vec![text("2")])])],
+//! );
+//! assert_eq!(2, view.query_selector_all("table > tr > td").len());
+//!
+//! view.query_selector_all_mut("td", |td| {
+//!     td.add_attributes_ref_mut(vec![class("cell")]);
+//! });
+//! assert_eq!(2, view.query_selector_all("td.cell").len());
+//! ```
+use crate::{
+    html::attributes::{
+        is_boolean_attr_enabled, merge_plain_values, AttributeValue,
+        HTML_BOOLEAN_ATTRS,
+    },
+    Node,
+};
+use std::{iter::Peekable, str::CharIndices, str::FromStr};
+use thiserror::Error;
+
+/// Query the nodes in the tree using css selectors
+pub trait QuerySelector<MSG> {
+    /// the first node in document order which matches the selector
+    ///
+    /// # Panics
+    /// when the selector is invalid, use `Selector::parse` to check the selector
+    fn query_selector(&self, selector: &str) -> Option<&Node<MSG>>;
+
+    /// all the nodes which matches the selector, in document order
+    ///
+    /// # Panics
+    /// when the selector is invalid
+    fn query_selector_all(&self, selector: &str) -> Vec<&Node<MSG>>;
+
+    /// mutable reference to the first node which matches the selector
+    ///
+    /// # Panics
+    /// when the selector is invalid
+    fn query_selector_mut(&mut self, selector: &str) -> Option<&mut Node<MSG>>;
+
+    /// call `f` with a mutable reference to each of the nodes which matches the selector.
+    /// A callback is used instead of returning the references since the matching
+    /// nodes can be nested inside each other.
+    /// The nodes are visited in reverse document order, so the descendants
+    /// are visited before their ancestors and the changes made by `f` into a node
+    /// do not affect the nodes which are not visited yet.
+    ///
+    /// # Panics
+    /// when the selector is invalid
+    fn query_selector_all_mut<F>(&mut self, selector: &str, f: F)
+    where
+        F: FnMut(&mut Node<MSG>);
+}
+
+impl<MSG> QuerySelector<MSG> for Node<MSG> {
+    fn query_selector(&self, selector: &str) -> Option<&Node<MSG>> {
+        parse_or_panic(selector).select_first(self)
+    }
+
+    fn query_selector_all(&self, selector: &str) -> Vec<&Node<MSG>> {
+        parse_or_panic(selector).select(self)
+    }
+
+    fn query_selector_mut(&mut self, selector: &str) -> Option<&mut Node<MSG>> {
+        let path = parse_or_panic(selector).paths(self, true).pop()?;
+        Some(node_at_mut(self, &path))
+    }
+
+    fn query_selector_all_mut<F>(&mut self, selector: &str, mut f: F)
+    where
+        F: FnMut(&mut Node<MSG>),
+    {
+        let paths = parse_or_panic(selector).paths(self, false);
+        for path in paths.iter().rev() {
+            f(node_at_mut(self, path));
+        }
+    }
+}
+
+fn parse_or_panic(selector: &str) -> Selector {
+    Selector::parse(selector).unwrap_or_else(|error| {
+        panic!("invalid selector {:?}: {}", selector, error)
+    })
+}
+
+/// the node at the path of child indexes from the root
+fn node_at_mut<'a, MSG>(
+    root: &'a mut Node<MSG>,
+    path: &[usize],
+) -> &'a mut Node<MSG> {
+    path.iter().fold(root, |node, index| {
+        &mut node
+            .children_mut()
+            .expect("the path must point to an element")[*index]
+    })
+}
+
+/// The error when parsing an invalid selector
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum SelectorError {
+    /// the selector has no compound selector
+    #[error("empty selector")]
+    Empty,
+    /// the character is not expected at this byte position
+    #[error("unexpected {0:?} at position {1}")]
+    Unexpected(char, usize),
+    /// the `[` of the attribute selector is not closed
+    #[error("unclosed attribute selector")]
+    UnclosedAttribute,
+    /// the selector ends with a combinator
+    #[error("missing selector after `>`")]
+    DanglingCombinator,
+}
+
+/// A parsed css selector
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector {
+    /// the compound selectors with the combinator to the one before it,
+    /// the combinator of the first one is not used
+    parts: Vec<(Combinator, Vec<Simple>)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Simple {
+    Universal,
+    Tag(String),
+    Id(String),
+    Class(String),
+    Attribute(String, Option<String>),
+}
+
+impl FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(selector: &str) -> Result<Self, Self::Err> {
+        Selector::parse(selector)
+    }
+}
+
+impl Selector {
+    /// parse the selector
+    pub fn parse(selector: &str) -> Result<Self, SelectorError> {
+        let mut chars = selector.char_indices().peekable();
+        let mut parts = vec![];
+        let mut combinator = Combinator::Descendant;
+        let mut has_combinator = false;
+        loop {
+            let has_space = skip_whitespace(&mut chars);
+            match chars.peek().copied() {
+                None => break,
+                Some((i, '>')) => {
+                    if parts.is_empty() || has_combinator {
+                        return Err(SelectorError::Unexpected('>', i));
+                    }
+                    chars.next();
+                    combinator = Combinator::Child;
+                    has_combinator = true;
+                }
+                Some((i, c)) => {
+                    if !parts.is_empty() && !has_space && !has_combinator {
+                        return Err(SelectorError::Unexpected(c, i));
+                    }
+                    let compound = parse_compound(&mut chars)?;
+                    if compound.is_empty() {
+                        return Err(SelectorError::Unexpected(c, i));
+                    }
+                    parts.push((combinator, compound));
+                    combinator = Combinator::Descendant;
+                    has_combinator = false;
+                }
+            }
+        }
+        if has_combinator {
+            Err(SelectorError::DanglingCombinator)
+        } else if parts.is_empty() {
+            Err(SelectorError::Empty)
+        } else {
+            Ok(Selector { parts })
+        }
+    }
+
+    /// all the nodes in the tree which matches this selector, in document order
+    pub fn select<'a, MSG>(&self, root: &'a Node<MSG>) -> Vec<&'a Node<MSG>> {
+        let mut found = vec![];
+        walk(root, &mut vec![], &mut |node, ancestors| {
+            if self.matches(node, ancestors) {
+                found.push(node);
+            }
+            false
+        });
+        found
+    }
+
+    /// the first node in the tree which matches this selector
+    pub fn select_first<'a, MSG>(
+        &self,
+        root: &'a Node<MSG>,
+    ) -> Option<&'a Node<MSG>> {
+        let mut found = None;
+        walk(root, &mut vec![], &mut |node, ancestors| {
+            if self.matches(node, ancestors) {
+                found = Some(node);
+            }
+            found.is_some()
+        });
+        found
+    }
+
+    /// returns true if the node matches this selector,
+    /// `ancestors` are the ancestors of the node starting from the root
+    pub fn matches<MSG>(
+        &self,
+        node: &Node<MSG>,
+        ancestors: &[&Node<MSG>],
+    ) -> bool {
+        matches_parts(&self.parts, node, ancestors)
+    }
+
+    /// the child index paths of the matching nodes, in document order
+    fn paths<MSG>(
+        &self,
+        root: &Node<MSG>,
+        first_only: bool,
+    ) -> Vec<Vec<usize>> {
+        let mut paths = vec![];
+        walk_paths(
+            root,
+            &mut vec![],
+            &mut vec![],
+            &mut |node, ancestors, path| {
+                if self.matches(node, ancestors) {
+                    paths.push(path.to_vec());
+                }
+                first_only && !paths.is_empty()
+            },
+        );
+        paths
+    }
+}
+
+/// returns true if there was a whitespace skipped
+fn skip_whitespace(chars: &mut Peekable<CharIndices>) -> bool {
+    let mut skipped = false;
+    while let Some((_, c)) = chars.peek() {
+        if !c.is_whitespace() {
+            break;
+        }
+        chars.next();
+        skipped = true;
+    }
+    skipped
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+fn parse_name(
+    chars: &mut Peekable<CharIndices>,
+) -> Result<String, SelectorError> {
+    let mut name = String::new();
+    while let Some((_, c)) = chars.peek() {
+        if !is_name_char(*c) {
+            break;
+        }
+        name.push(*c);
+        chars.next();
+    }
+    if name.is_empty() {
+        match chars.peek() {
+            Some((i, c)) => Err(SelectorError::Unexpected(*c, *i)),
+            None => Err(SelectorError::Empty),
+        }
+    } else {
+        Ok(name)
+    }
+}
+
+fn parse_compound(
+    chars: &mut Peekable<CharIndices>,
+) -> Result<Vec<Simple>, SelectorError> {
+    let mut compound = vec![];
+    while let Some((i, c)) = chars.peek().copied() {
+        let simple = match c {
+            '*' if compound.is_empty() => {
+                chars.next();
+                Simple::Universal
+            }
+            '#' => {
+                chars.next();
+                Simple::Id(parse_name(chars)?)
+            }
+            '.' => {
+                chars.next();
+                Simple::Class(parse_name(chars)?)
+            }
+            '[' => {
+                chars.next();
+                parse_attribute(chars)?
+            }
+            c if is_name_char(c) && compound.is_empty() => {
+                Simple::Tag(parse_name(chars)?)
+            }
+            c if c.is_whitespace() || c == '>' => break,
+            _ => return Err(SelectorError::Unexpected(c, i)),
+        };
+        compound.push(simple);
+    }
+    Ok(compound)
+}
+
+/// parse the attribute selector after the `[`
+fn parse_attribute(
+    chars: &mut Peekable<CharIndices>,
+) -> Result<Simple, SelectorError> {
+    skip_whitespace(chars);
+    let name = parse_name(chars)?;
+    skip_whitespace(chars);
+    let value = match chars.next() {
+        Some((_, ']')) => return Ok(Simple::Attribute(name, None)),
+        Some((_, '=')) => {
+            skip_whitespace(chars);
+            parse_attribute_value(chars)?
+        }
+        Some((i, c)) => return Err(SelectorError::Unexpected(c, i)),
+        None => return Err(SelectorError::UnclosedAttribute),
+    };
+    skip_whitespace(chars);
+    match chars.next() {
+        Some((_, ']')) => Ok(Simple::Attribute(name, Some(value))),
+        Some((i, c)) => Err(SelectorError::Unexpected(c, i)),
+        None => Err(SelectorError::UnclosedAttribute),
+    }
+}
+
+/// the value is either quoted or a name
+fn parse_attribute_value(
+    chars: &mut Peekable<CharIndices>,
+) -> Result<String, SelectorError> {
+    match chars.peek().copied() {
+        Some((_, quote)) if quote == '"' || quote == '\'' => {
+            chars.next();
+            let mut value = String::new();
+            for (_, c) in chars {
+                if c == quote {
+                    return Ok(value);
+                }
+                value.push(c);
+            }
+            Err(SelectorError::UnclosedAttribute)
+        }
+        Some(_) => parse_name(chars),
+        None => Err(SelectorError::UnclosedAttribute),
+    }
+}
+
+/// match the last compound to the node and the rest to its ancestors
+fn matches_parts<MSG>(
+    parts: &[(Combinator, Vec<Simple>)],
+    node: &Node<MSG>,
+    ancestors: &[&Node<MSG>],
+) -> bool {
+    let ((combinator, compound), rest) = match parts.split_last() {
+        Some(last) => last,
+        None => return true,
+    };
+    if !compound.iter().all(|simple| matches_simple(simple, node)) {
+        return false;
+    }
+    if rest.is_empty() {
+        return true;
+    }
+    match combinator {
+        Combinator::Child => match ancestors.split_last() {
+            Some((parent, ancestors)) => matches_parts(rest, parent, ancestors),
+            None => false,
+        },
+        Combinator::Descendant => (0..ancestors.len())
+            .rev()
+            .any(|i| matches_parts(rest, ancestors[i], &ancestors[..i])),
+    }
+}
+
+fn matches_simple<MSG>(simple: &Simple, node: &Node<MSG>) -> bool {
+    let tag = match node.tag() {
+        Some(tag) => tag,
+        None => return false,
+    };
+    match simple {
+        Simple::Universal => true,
+        Simple::Tag(name) => tag.eq_ignore_ascii_case(name),
+        Simple::Id(id) => attribute_value(node, "id").as_deref() == Some(id),
+        Simple::Class(class) => attribute_value(node, "class")
+            .map(|classes| classes.split_whitespace().any(|c| c == class))
+            .unwrap_or(false),
+        Simple::Attribute(name, None) => has_attribute(node, name),
+        Simple::Attribute(name, Some(value)) => {
+            has_attribute(node, name)
+                && attribute_value(node, name).as_deref() == Some(value)
+        }
+    }
+}
+
+/// the boolean attributes which are disabled are not set in the DOM
+fn has_attribute<MSG>(node: &Node<MSG>, name: &str) -> bool {
+    let values = plain_values(node, name);
+    if HTML_BOOLEAN_ATTRS.contains(&name) {
+        is_boolean_attr_enabled(&values)
+    } else {
+        !values.is_empty()
+    }
+}
+
+fn plain_values<'a, MSG>(
+    node: &'a Node<MSG>,
+    name: &str,
+) -> Vec<&'a AttributeValue> {
+    node.get_attributes()
+        .unwrap_or_default()
+        .iter()
+        .filter(|attr| *attr.name() == name)
+        .flat_map(|attr| attr.get_plain())
+        .collect()
+}
+
+/// the merged value of the attribute of the node, the same value which is set in the DOM
+pub fn attribute_value<MSG>(node: &Node<MSG>, name: &str) -> Option<String> {
+    merge_plain_values(name, &plain_values(node, name))
+}
+
+/// called with the node and its ancestors, returns true to stop the walk
+type Visit<'v, 'a, MSG> =
+    dyn FnMut(&'a Node<MSG>, &[&'a Node<MSG>]) -> bool + 'v;
+
+/// called with the node, its ancestors and its path, returns true to stop the walk
+type VisitPath<'v, 'a, MSG> =
+    dyn FnMut(&'a Node<MSG>, &[&'a Node<MSG>], &[usize]) -> bool + 'v;
+
+/// visit the nodes in document order until `f` returns true
+fn walk<'a, MSG>(
+    node: &'a Node<MSG>,
+    ancestors: &mut Vec<&'a Node<MSG>>,
+    f: &mut Visit<'_, 'a, MSG>,
+) -> bool {
+    walk_paths(
+        node,
+        ancestors,
+        &mut vec![],
+        &mut |node, ancestors, _path| f(node, ancestors),
+    )
+}
+
+/// visit the nodes in document order together with their path until `f` returns true
+fn walk_paths<'a, MSG>(
+    node: &'a Node<MSG>,
+    ancestors: &mut Vec<&'a Node<MSG>>,
+    path: &mut Vec<usize>,
+    f: &mut VisitPath<'_, 'a, MSG>,
+) -> bool {
+    if f(node, ancestors, path) {
+        return true;
+    }
+    ancestors.push(node);
+    for (index, child) in
+        node.get_children().unwrap_or_default().iter().enumerate()
+    {
+        path.push(index);
+        let stop = walk_paths(child, ancestors, path, f);
+        path.pop();
+        if stop {
+            ancestors.pop();
+            return true;
+        }
+    }
+    ancestors.pop();
+    false
+}
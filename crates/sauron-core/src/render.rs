@@ -2,21 +2,140 @@
 //! virtual dom into a writable buffer
 //!
 use crate::{
-    html::attributes::AttributeValue, mt_dom::AttValue, Attribute, Element,
-    Node,
+    html::{
+        attributes::{
+            is_boolean_attr_enabled, merge_plain_values, AttributeValue,
+            HTML_BOOLEAN_ATTRS,
+        },
+        tags::HTML_VOID_ELEMENTS,
+    },
+    mt_dom,
+    svg::SVG_NAMESPACE,
+    Attribute, Element, Node,
 };
-use std::fmt;
+use std::fmt::{self, Write};
+
+pub use stream::{HtmlWriter, RenderChunks};
+
+mod stream;
+
+/// The elements whose text content is written as is, without escaping
+const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+/// The options of how the html is written
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderOptions {
+    /// write each child node in its own indented line,
+    /// otherwise no whitespace is added, which keeps the inline layout intact
+    pub pretty: bool,
+    /// the number of spaces for each level of indentation in pretty mode
+    pub indent: usize,
+    /// start the output with `<!DOCTYPE html>`
+    pub doctype: bool,
+    /// write the void elements as `<br/>` and the boolean attributes
+    /// as `checked="checked"`, otherwise they are written as `<br>` and `checked`
+    pub xhtml: bool,
+    /// write the attributes sorted by name and the class names sorted,
+    /// so the output doesn't depend on the order they are added in the view
+    pub sort_attributes: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self::pretty()
+    }
+}
+
+impl RenderOptions {
+    /// indented output with 4 spaces
+    pub fn pretty() -> Self {
+        RenderOptions {
+            pretty: true,
+            indent: 4,
+            doctype: false,
+            xhtml: false,
+            sort_attributes: false,
+        }
+    }
+
+    /// output without any added whitespace
+    pub fn compact() -> Self {
+        RenderOptions {
+            pretty: false,
+            indent: 0,
+            doctype: false,
+            xhtml: false,
+            sort_attributes: false,
+        }
+    }
+
+    /// use this number of spaces for each level of indentation
+    pub fn with_indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// render as a whole document, starting with `<!DOCTYPE html>`
+    pub fn with_doctype(mut self) -> Self {
+        self.doctype = true;
+        self
+    }
+
+    /// write the void elements and boolean attributes in XHTML syntax
+    pub fn with_xhtml(mut self) -> Self {
+        self.xhtml = true;
+        self
+    }
+
+    /// sort the attributes and the class names
+    pub fn with_sorted_attributes(mut self) -> Self {
+        self.sort_attributes = true;
+        self
+    }
+
+    /// write a new line followed by the indentation in pretty mode
+    fn write_new_line(
+        &self,
+        buffer: &mut dyn fmt::Write,
+        indent: usize,
+    ) -> fmt::Result {
+        if self.pretty {
+            write!(buffer, "\n{}", " ".repeat(self.indent * indent))?;
+        }
+        Ok(())
+    }
+}
 
 /// render node, elements to a writable buffer
 pub trait Render {
     /// render the node to a writable buffer
     fn render(&self, buffer: &mut dyn fmt::Write) -> fmt::Result {
-        self.render_with_indent(buffer, 0)
+        self.render_with_options(buffer, &RenderOptions::default())
+    }
+
+    /// render the node to a writable buffer without any added whitespace
+    fn render_compact(&self, buffer: &mut dyn fmt::Write) -> fmt::Result {
+        self.render_with_options(buffer, &RenderOptions::compact())
+    }
+
+    /// render the node to a writable buffer using the options
+    fn render_with_options(
+        &self,
+        buffer: &mut dyn fmt::Write,
+        options: &RenderOptions,
+    ) -> fmt::Result {
+        if options.doctype {
+            write!(buffer, "<!DOCTYPE html>")?;
+            options.write_new_line(buffer, 0)?;
+        }
+        self.render_with_indent(buffer, options, 0)
     }
+
     /// render instance to a writable buffer with indention
     fn render_with_indent(
         &self,
         buffer: &mut dyn fmt::Write,
+        options: &RenderOptions,
         indent: usize,
     ) -> fmt::Result;
 }
@@ -25,13 +144,14 @@ impl<MSG> Render for Node<MSG> {
     fn render_with_indent(
         &self,
         buffer: &mut dyn fmt::Write,
+        options: &RenderOptions,
         indent: usize,
     ) -> fmt::Result {
         match self {
             Node::Element(element) => {
-                element.render_with_indent(buffer, indent)
+                element.render_with_indent(buffer, options, indent)
             }
-            Node::Text(text) => write!(buffer, "{}", text),
+            Node::Text(text) => write!(Escaped::text(buffer), "{}", text),
         }
     }
 }
@@ -40,41 +160,94 @@ impl<MSG> Render for Element<MSG> {
     fn render_with_indent(
         &self,
         buffer: &mut dyn fmt::Write,
+        options: &RenderOptions,
         indent: usize,
     ) -> fmt::Result {
-        write!(buffer, "<{}", self.tag())?;
-
-        // TODO: merge attributes with the same name first
-        for attr in self.get_attributes() {
-            write!(buffer, " ")?;
-            attr.render_with_indent(buffer, indent)?;
+        if !write_start_tag(self, buffer, options)? {
+            return Ok(());
         }
-        write!(buffer, ">")?;
-
+        let is_raw_text = is_raw_text(self);
         let children = self.get_children();
-        let first_child = children.get(0);
-        let is_first_child_text_node =
-            first_child.map(|node| node.is_text()).unwrap_or(false);
-
-        let is_lone_child_text_node =
-            children.len() == 1 && is_first_child_text_node;
 
         // do not indent if it is only text child node
-        if is_lone_child_text_node {
-            first_child.unwrap().render_with_indent(buffer, indent)?;
+        if is_lone_text_child(self) {
+            write_child(buffer, &children[0], options, indent, is_raw_text)?;
         } else {
             // otherwise print all child nodes with each line and indented
-            for child in self.get_children() {
-                write!(buffer, "\n{}", "    ".repeat(indent + 1))?;
-                child.render_with_indent(buffer, indent + 1)?;
+            for child in children {
+                options.write_new_line(buffer, indent + 1)?;
+                write_child(buffer, child, options, indent + 1, is_raw_text)?;
+            }
+            // do not make a new line if it has no child nodes
+            if !children.is_empty() {
+                options.write_new_line(buffer, indent)?;
             }
         }
-        // do not make a new line it if is only a text child node or it has no child nodes
-        if !is_lone_child_text_node && !children.is_empty() {
-            write!(buffer, "\n{}", "    ".repeat(indent))?;
+        write!(buffer, "</{}>", self.tag())
+    }
+}
+
+/// write the opening tag of the element with its attributes,
+/// returns false if the element is already closed
+/// and has no child nodes nor closing tag to be written
+fn write_start_tag<MSG>(
+    element: &Element<MSG>,
+    buffer: &mut dyn fmt::Write,
+    options: &RenderOptions,
+) -> Result<bool, fmt::Error> {
+    write!(buffer, "<{}", element.tag())?;
+
+    // the attributes with the same name are merged the same way as in the DOM
+    let mut attrs = mt_dom::merge_attributes_of_same_name(
+        &element.get_attributes().iter().collect::<Vec<_>>(),
+    );
+    if options.sort_attributes {
+        attrs.sort_by_key(|attr| *attr.name());
+    }
+    for attr in attrs.iter() {
+        if is_rendered(attr) {
+            write!(buffer, " ")?;
+            attr.render_with_indent(buffer, options, 0)?;
         }
-        write!(buffer, "</{}>", self.tag())?;
-        Ok(())
+    }
+
+    // the void elements can not have children, so they are never closed.
+    // Any element in svg can be self-closing.
+    let is_void = HTML_VOID_ELEMENTS.contains(element.tag());
+    let is_svg = element.namespace() == Some(&SVG_NAMESPACE);
+    if is_void && !options.xhtml {
+        write!(buffer, ">")?;
+        Ok(false)
+    } else if is_void || (is_svg && element.get_children().is_empty()) {
+        write!(buffer, "/>")?;
+        Ok(false)
+    } else {
+        write!(buffer, ">")?;
+        Ok(true)
+    }
+}
+
+/// the text of script and style elements are written as is
+fn is_raw_text<MSG>(element: &Element<MSG>) -> bool {
+    RAW_TEXT_ELEMENTS.contains(element.tag())
+}
+
+/// a lone text child is written in the same line as the element
+fn is_lone_text_child<MSG>(element: &Element<MSG>) -> bool {
+    let children = element.get_children();
+    children.len() == 1 && children[0].is_text()
+}
+
+fn write_child<MSG>(
+    buffer: &mut dyn fmt::Write,
+    child: &Node<MSG>,
+    options: &RenderOptions,
+    indent: usize,
+    is_raw_text: bool,
+) -> fmt::Result {
+    match child {
+        Node::Text(text) if is_raw_text => write!(buffer, "{}", text),
+        _ => child.render_with_indent(buffer, options, indent),
     }
 }
 
@@ -82,22 +255,30 @@ impl<MSG> Render for Attribute<MSG> {
     fn render_with_indent(
         &self,
         buffer: &mut dyn fmt::Write,
-        indent: usize,
+        options: &RenderOptions,
+        _indent: usize,
     ) -> fmt::Result {
-        write!(buffer, "{}=\"", self.name())?;
-        for (i, att_value) in self.value().iter().enumerate() {
-            match att_value {
-                AttValue::Plain(plain) => {
-                    if i > 0 && !plain.is_style() {
-                        write!(buffer, " ")?;
-                    }
-                    plain.render_with_indent(buffer, indent)?;
-                }
-                _ => (),
+        if !is_rendered(self) {
+            return Ok(());
+        }
+        if HTML_BOOLEAN_ATTRS.contains(self.name()) {
+            write!(buffer, "{}", self.name())?;
+            if options.xhtml {
+                write!(buffer, "=\"{}\"", self.name())?;
             }
+            return Ok(());
         }
-        write!(buffer, "\"")?;
-        Ok(())
+        let mut value = merge_plain_values(self.name(), &rendered_values(self))
+            .unwrap_or_default();
+        if options.sort_attributes && *self.name() == "class" {
+            let mut classes: Vec<&str> = value.split_whitespace().collect();
+            classes.sort_unstable();
+            classes.dedup();
+            value = classes.join(" ");
+        }
+        write!(buffer, "{}=\"", self.name())?;
+        write!(Escaped::attribute(buffer), "{}", value)?;
+        write!(buffer, "\"")
     }
 }
 
@@ -105,6 +286,7 @@ impl Render for AttributeValue {
     fn render_with_indent(
         &self,
         buffer: &mut dyn fmt::Write,
+        _options: &RenderOptions,
         _index: usize,
     ) -> fmt::Result {
         match self {
@@ -121,3 +303,70 @@ impl Render for AttributeValue {
         Ok(())
     }
 }
+
+/// the values of the attribute which are written into the html,
+/// the callbacks, function calls and empty values are not written
+fn rendered_values<MSG>(attr: &Attribute<MSG>) -> Vec<&AttributeValue> {
+    attr.get_plain()
+        .into_iter()
+        .filter(|value| {
+            matches!(
+                value,
+                AttributeValue::Simple(_) | AttributeValue::Style(_)
+            )
+        })
+        .collect()
+}
+
+/// returns false if the attribute has nothing to be written or
+/// it is a boolean attribute which is disabled
+fn is_rendered<MSG>(attr: &Attribute<MSG>) -> bool {
+    let values = rendered_values(attr);
+    if HTML_BOOLEAN_ATTRS.contains(attr.name()) {
+        is_boolean_attr_enabled(&values)
+    } else {
+        !values.is_empty()
+    }
+}
+
+/// A writer which escapes the html special characters before writing into the buffer
+struct Escaped<'a> {
+    buffer: &'a mut dyn fmt::Write,
+    /// also escape the `"`, for text inside the attribute values
+    escape_quote: bool,
+}
+
+impl<'a> Escaped<'a> {
+    fn text(buffer: &'a mut dyn fmt::Write) -> Self {
+        Escaped {
+            buffer,
+            escape_quote: false,
+        }
+    }
+
+    fn attribute(buffer: &'a mut dyn fmt::Write) -> Self {
+        Escaped {
+            buffer,
+            escape_quote: true,
+        }
+    }
+}
+
+impl<'a> fmt::Write for Escaped<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut start = 0;
+        for (i, c) in s.char_indices() {
+            let escaped = match c {
+                '&' => "&amp;",
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '"' if self.escape_quote => "&quot;",
+                _ => continue,
+            };
+            self.buffer.write_str(&s[start..i])?;
+            self.buffer.write_str(escaped)?;
+            start = i + 1;
+        }
+        self.buffer.write_str(&s[start..])
+    }
+}
@@ -0,0 +1,225 @@
+//! Provides streaming of the rendered html, so a big page can be sent
+//! while it is still being rendered instead of building it fully into a String first.
+//!
+use super::{
+    is_lone_text_child, is_raw_text, write_start_tag, Escaped, RenderOptions,
+};
+use crate::{Element, Node};
+use std::{
+    fmt::{self, Write},
+    io, mem,
+};
+
+/// the default size of the chunks in bytes
+const DEFAULT_CHUNK_SIZE: usize = 8 * 1024;
+
+/// Writes the rendered html into an `io::Write` in chunks,
+/// the chunk is written and the writer is flushed everytime the chunk is full.
+///
+/// Example:
+/// ```rust
+/// use sauron_core::{html::*, HtmlWriter, Node, Render};
+///
+/// let node: Node<()> = div(vec![], vec![text("hello")]);
+/// let mut writer = HtmlWriter::new(Vec::new());
+/// node.render_compact(&mut writer).expect("must render");
+/// let bytes = writer.finish().expect("must write");
+/// assert_eq!(bytes, b"<div>hello</div>");
+/// ```
+pub struct HtmlWriter<W: io::Write> {
+    writer: W,
+    chunk: String,
+    chunk_size: usize,
+    /// the io error which stopped the rendering, returned in `finish`
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> HtmlWriter<W> {
+    /// create a writer which writes in chunks of 8KB
+    pub fn new(writer: W) -> Self {
+        Self::with_chunk_size(writer, DEFAULT_CHUNK_SIZE)
+    }
+
+    /// create a writer which writes in chunks of `chunk_size` bytes
+    pub fn with_chunk_size(writer: W, chunk_size: usize) -> Self {
+        HtmlWriter {
+            writer,
+            chunk: String::with_capacity(chunk_size),
+            chunk_size,
+            error: None,
+        }
+    }
+
+    /// write the remaining html and flush the writer, then return it.
+    /// This returns the io error which made the rendering fail.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        self.write_chunk()?;
+        Ok(self.writer)
+    }
+
+    fn write_chunk(&mut self) -> io::Result<()> {
+        self.writer.write_all(self.chunk.as_bytes())?;
+        self.writer.flush()?;
+        self.chunk.clear();
+        Ok(())
+    }
+}
+
+impl<W: io::Write> fmt::Write for HtmlWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.error.is_some() {
+            return Err(fmt::Error);
+        }
+        self.chunk.push_str(s);
+        if self.chunk.len() >= self.chunk_size {
+            if let Err(error) = self.write_chunk() {
+                self.error = Some(error);
+                return Err(fmt::Error);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An iterator which renders the node lazily into chunks of html bytes.
+/// Only the part of the node needed for the next chunk is rendered,
+/// so the chunks can be sent as they are produced.
+///
+/// Example:
+/// ```rust
+/// use sauron_core::{html::*, Node, RenderChunks, RenderOptions};
+///
+/// let items = (0..1000).map(|i| li(vec![], vec![text(i)])).collect();
+/// let node: Node<()> = ul(vec![], items);
+/// let chunks = RenderChunks::new(&node, RenderOptions::compact(), 1024);
+/// let html: Vec<u8> = chunks.flatten().collect();
+/// assert!(html.starts_with(b"<ul><li>0</li>"));
+/// ```
+pub struct RenderChunks<'a, MSG> {
+    /// the remaining work, the last one is done next
+    stack: Vec<Step<'a, MSG>>,
+    options: RenderOptions,
+    chunk_size: usize,
+    chunk: String,
+}
+
+enum Step<'a, MSG> {
+    Node {
+        node: &'a Node<MSG>,
+        indent: usize,
+        is_raw_text: bool,
+    },
+    NewLine(usize),
+    EndTag(&'a Element<MSG>),
+}
+
+impl<'a, MSG> RenderChunks<'a, MSG> {
+    /// render the node with the options into chunks of at least `chunk_size` bytes,
+    /// except for the last one
+    pub fn new(
+        node: &'a Node<MSG>,
+        options: RenderOptions,
+        chunk_size: usize,
+    ) -> Self {
+        let mut chunk = String::with_capacity(chunk_size);
+        if options.doctype {
+            chunk.push_str("<!DOCTYPE html>");
+            options
+                .write_new_line(&mut chunk, 0)
+                .expect("must write to string");
+        }
+        RenderChunks {
+            stack: vec![Step::Node {
+                node,
+                indent: 0,
+                is_raw_text: false,
+            }],
+            options,
+            chunk_size,
+            chunk,
+        }
+    }
+
+    /// render the next step into the chunk
+    fn render_step(&mut self, step: Step<'a, MSG>) -> fmt::Result {
+        match step {
+            Step::NewLine(indent) => {
+                self.options.write_new_line(&mut self.chunk, indent)
+            }
+            Step::EndTag(element) => {
+                write!(self.chunk, "</{}>", element.tag())
+            }
+            Step::Node {
+                node: Node::Text(text),
+                is_raw_text,
+                ..
+            } => {
+                if is_raw_text {
+                    write!(self.chunk, "{}", text)
+                } else {
+                    write!(Escaped::text(&mut self.chunk), "{}", text)
+                }
+            }
+            Step::Node {
+                node: Node::Element(element),
+                indent,
+                ..
+            } => {
+                if !write_start_tag(element, &mut self.chunk, &self.options)? {
+                    return Ok(());
+                }
+                let is_raw_text = is_raw_text(element);
+                let children = element.get_children();
+                // the steps are pushed in reverse, since the last one is done first
+                self.stack.push(Step::EndTag(element));
+                if is_lone_text_child(element) {
+                    self.stack.push(Step::Node {
+                        node: &children[0],
+                        indent,
+                        is_raw_text,
+                    });
+                } else {
+                    if !children.is_empty() {
+                        self.stack.push(Step::NewLine(indent));
+                    }
+                    for child in children.iter().rev() {
+                        self.stack.push(Step::Node {
+                            node: child,
+                            indent: indent + 1,
+                            is_raw_text,
+                        });
+                        self.stack.push(Step::NewLine(indent + 1));
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'a, MSG> Iterator for RenderChunks<'a, MSG> {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.chunk.len() < self.chunk_size {
+            match self.stack.pop() {
+                Some(step) => {
+                    self.render_step(step).expect("must write to string")
+                }
+                None => break,
+            }
+        }
+        if self.chunk.is_empty() {
+            None
+        } else {
+            let chunk = mem::replace(
+                &mut self.chunk,
+                String::with_capacity(self.chunk_size),
+            );
+            Some(chunk.into_bytes())
+        }
+    }
+}
@@ -0,0 +1,324 @@
+//! provides typed routing, where the url is parsed into a user defined route.
+//!
+//! The url parsing is available without the `with-dom` feature, so the same routes
+//! can be matched when rendering in the server.
+//! With `with-dom`, the `Router` provides navigation using the History API.
+//!
+//! Example:
+//! ```rust
+//! use sauron_core::router::{Route, Url};
+//!
+//! #[derive(Debug, PartialEq, Clone)]
+//! enum Page {
+//!     Home,
+//!     Article(u32),
+//!     Search(String),
+//! }
+//!
+//! impl Route for Page {
+//!     fn from_url(url: &Url) -> Option<Self> {
+//!         match url.segments().as_slice() {
+//!             [] => Some(Page::Home),
+//!             ["article", id] => id.parse().ok().map(Page::Article),
+//!             ["search"] => Some(Page::Search(url.query("q")?.to_string())),
+//!             _ => None,
+//!         }
+//!     }
+//!
+//!     fn to_url(&self) -> String {
+//!         match self {
+//!             Page::Home => "/".to_string(),
+//!             Page::Article(id) => format!("/article/{}", id),
+//!             Page::Search(q) => Url::new("/search").with_query("q", q).to_string(),
+//!         }
+//!     }
+//! }
+//!
+//! let url = Url::parse("/search?q=hello%20world");
+//! assert_eq!(Page::from_url(&url), Some(Page::Search("hello world".to_string())));
+//! ```
+use std::fmt;
+
+/// A route which can be parsed from a url and converted back into a url
+pub trait Route: Sized {
+    /// parse the url into this route, returns None if the url doesn't match any route
+    fn from_url(url: &Url) -> Option<Self>;
+
+    /// the url of this route, this is used as the href of the links
+    fn to_url(&self) -> String;
+}
+
+/// The path, query and hash of a url, with the query values and hash decoded
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Url {
+    /// the path of the url, starting with `/`
+    pub path: String,
+    /// the decoded name and value pairs of the query string
+    pub query: Vec<(String, String)>,
+    /// the decoded text after the `#` sign
+    pub hash: Option<String>,
+}
+
+impl Url {
+    /// create a url with only the path
+    pub fn new(path: &str) -> Self {
+        Url {
+            path: path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// parse the url, the scheme and host are ignored if present
+    pub fn parse(url: &str) -> Self {
+        let url = match url.find("://") {
+            Some(scheme_end) => {
+                let after_scheme = &url[scheme_end + 3..];
+                match after_scheme.find(['/', '?', '#']) {
+                    Some(host_end) => &after_scheme[host_end..],
+                    None => "",
+                }
+            }
+            None => url,
+        };
+        let (url, hash) = match url.find('#') {
+            Some(i) => (&url[..i], Some(decode(&url[i + 1..]))),
+            None => (url, None),
+        };
+        let (path, query) = match url.find('?') {
+            Some(i) => (&url[..i], parse_query(&url[i + 1..])),
+            None => (url, vec![]),
+        };
+        let path = if path.starts_with('/') {
+            path.to_string()
+        } else {
+            format!("/{}", path)
+        };
+        Url { path, query, hash }
+    }
+
+    /// parse the hash part of this url as a url,
+    /// this is used for apps which are routed with `#/...` fragments
+    pub fn hash_url(&self) -> Url {
+        Url::parse(self.hash.as_deref().unwrap_or_default())
+    }
+
+    /// the non-empty segments of the path
+    pub fn segments(&self) -> Vec<&str> {
+        self.path.split('/').filter(|s| !s.is_empty()).collect()
+    }
+
+    /// the value of the first query parameter with this name
+    pub fn query(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// add a query parameter to this url
+    pub fn with_query(mut self, name: &str, value: &str) -> Self {
+        self.query.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// set the hash of this url
+    pub fn with_hash(mut self, hash: &str) -> Self {
+        self.hash = Some(hash.to_string());
+        self
+    }
+}
+
+impl fmt::Display for Url {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.path)?;
+        for (i, (name, value)) in self.query.iter().enumerate() {
+            let separator = if i == 0 { '?' } else { '&' };
+            write!(f, "{}{}={}", separator, encode(name), encode(value))?;
+        }
+        if let Some(hash) = &self.hash {
+            write!(f, "#{}", encode(hash))?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.find('=') {
+            Some(i) => (decode(&pair[..i]), decode(&pair[i + 1..])),
+            None => (decode(pair), String::new()),
+        })
+        .collect()
+}
+
+/// percent-encode everything except the unreserved characters and `/`
+pub(crate) fn encode(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'~'
+            | b'/' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// decode the percent-encoded text, `+` is decoded as space.
+/// Invalid escapes are kept as is.
+fn decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                match (hex_value(bytes[i + 1]), hex_value(bytes[i + 2])) {
+                    (Some(high), Some(low)) => {
+                        decoded.push(high * 16 + low);
+                        i += 2;
+                    }
+                    _ => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    (byte as char).to_digit(16).map(|v| v as u8)
+}
+
+#[cfg(feature = "with-dom")]
+pub use dom_router::Router;
+
+#[cfg(feature = "with-dom")]
+mod dom_router {
+    use super::{Route, Url};
+    use crate::{
+        html::{a, attributes::href, events::on_filter_map},
+        Attribute, Cmd, Component, Node, Sub,
+    };
+    use wasm_bindgen::{JsCast, JsValue};
+    use web_sys::MouseEvent;
+
+    /// provides navigation using the History API of the browser
+    #[derive(Copy, Clone, Debug)]
+    pub struct Router;
+
+    impl Router {
+        /// the url of the current location of the browser
+        pub fn current_url() -> Url {
+            let location = crate::window().location();
+            let path = location.pathname().expect("must have a pathname");
+            let search = location.search().expect("must have a search");
+            let hash = location.hash().expect("must have a hash");
+            Url::parse(&format!("{}{}{}", path, search, hash))
+        }
+
+        /// the route matching the current location of the browser
+        pub fn current_route<R: Route>() -> Option<R> {
+            R::from_url(&Self::current_url())
+        }
+
+        /// push the url of the route into the browser history,
+        /// this doesn't reload the page nor trigger the `popstate` event
+        pub fn push<R, APP, MSG>(route: &R) -> Cmd<APP, MSG>
+        where
+            R: Route,
+            MSG: 'static,
+            APP: Component<MSG> + 'static,
+        {
+            let url = route.to_url();
+            Cmd::new(move |_program| push_url(&url))
+        }
+
+        /// replace the current entry of the browser history with the url of the route
+        pub fn replace<R, APP, MSG>(route: &R) -> Cmd<APP, MSG>
+        where
+            R: Route,
+            MSG: 'static,
+            APP: Component<MSG> + 'static,
+        {
+            let url = route.to_url();
+            Cmd::new(move |_program| {
+                crate::history()
+                    .replace_state_with_url(&JsValue::NULL, "", Some(&url))
+                    .expect("Unable to replace history state");
+            })
+        }
+
+        /// emit the MSG when the user navigates with the back and forward
+        /// buttons of the browser, the function is supplied with the route of the new location
+        pub fn on_popstate<R, F, MSG>(f: F) -> Sub<MSG>
+        where
+            R: Route,
+            F: Fn(Option<R>) -> MSG + 'static,
+            MSG: 'static,
+        {
+            Sub::on_window("popstate", move |_| f(Self::current_route()))
+        }
+
+        /// an `a` link to the route, clicking it pushes the url into the history
+        /// and emits the MSG from `on_navigate` instead of reloading the page.
+        ///
+        /// Clicking with a modifier key or a button other than the main button
+        /// is left to the browser, such as opening the link in a new tab,
+        /// and no MSG is emitted.
+        pub fn link<R, F, MSG>(
+            route: &R,
+            on_navigate: F,
+            attrs: Vec<Attribute<MSG>>,
+            children: Vec<Node<MSG>>,
+        ) -> Node<MSG>
+        where
+            R: Route + Clone + 'static,
+            F: Fn(R) -> MSG + 'static,
+            MSG: 'static,
+        {
+            let url = route.to_url();
+            let route = route.clone();
+            let link_url = url.clone();
+            a(
+                vec![
+                    href(url),
+                    on_filter_map("click", move |event| {
+                        if let Some(mouse) = event.dyn_ref::<MouseEvent>() {
+                            if mouse.ctrl_key()
+                                || mouse.meta_key()
+                                || mouse.shift_key()
+                                || mouse.alt_key()
+                                || mouse.button() != 0
+                            {
+                                return None;
+                            }
+                        }
+                        event.prevent_default();
+                        push_url(&link_url);
+                        Some(on_navigate(route.clone()))
+                    }),
+                ],
+                children,
+            )
+            .add_attributes(attrs)
+        }
+    }
+
+    fn push_url(url: &str) {
+        crate::history()
+            .push_state_with_url(&JsValue::NULL, "", Some(url))
+            .expect("Unable to push history state");
+    }
+}
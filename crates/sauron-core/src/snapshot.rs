@@ -0,0 +1,241 @@
+//! Provides snapshot testing of the views.
+//!
+//! The view is rendered into a normalized form, with each node in its own line
+//! and the attributes and class names sorted, then compared with the snapshot
+//! stored in a `.snap` file. When they don't match, the test fails with a diff
+//! of the lines showing where in the tree the changes are.
+//!
+//! Run the tests with `SAURON_UPDATE_SNAPSHOTS=1` to write the new
+//! or changed snapshots into their files.
+//!
+//! Example:
+//! ```rust,no_run
+//! use sauron_core::{assert_snapshot, html::{attributes::*, *}, Node};
+//!
+//! let view: Node<()> = ul(vec![class("list")], vec![li(vec![], vec![text("one")])]);
+//! // compared with `tests/snapshots/list.snap` of the crate
+//! assert_snapshot!("list", &view);
+//! ```
+use crate::{Node, Render, RenderOptions};
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// the environment variable which, when set, writes the snapshots instead of comparing them
+pub const UPDATE_SNAPSHOTS: &str = "SAURON_UPDATE_SNAPSHOTS";
+
+/// the number of unchanged lines shown around the changed lines in the diff
+const CONTEXT_LINES: usize = 2;
+
+/// The reason a snapshot check failed
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    /// the snapshot file does not exist yet
+    #[error("snapshot {} does not exist, run the test with SAURON_UPDATE_SNAPSHOTS=1 to create it", .0.display())]
+    Missing(PathBuf),
+    /// the view is different from the snapshot
+    #[error("snapshot {} does not match, run the test with SAURON_UPDATE_SNAPSHOTS=1 to update it\n{}", .path.display(), .diff)]
+    Mismatch {
+        /// the snapshot file
+        path: PathBuf,
+        /// the diff of the snapshot and the view
+        diff: String,
+    },
+    /// the snapshot file can not be read or written
+    #[error("unable to access snapshot {}: {}", .0.display(), .1)]
+    Io(PathBuf, io::Error),
+}
+
+/// compare the node with the snapshot in the `tests/snapshots` directory
+/// of the crate, the file name is the name with the `.snap` extension.
+/// Panics with the diff when they don't match.
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $node:expr) => {
+        $crate::snapshot::assert_snapshot(
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("snapshots")
+                .join(format!("{}.snap", $name)),
+            $node,
+        )
+    };
+}
+
+/// the normalized html of the node which is stored in the snapshots
+pub fn normalize<MSG>(node: &Node<MSG>) -> String {
+    let options = RenderOptions::pretty()
+        .with_indent(2)
+        .with_sorted_attributes();
+    let mut buffer = String::new();
+    node.render_with_options(&mut buffer, &options)
+        .expect("must write to string");
+    buffer
+}
+
+/// compare the node with the snapshot file,
+/// the snapshot is written instead when `SAURON_UPDATE_SNAPSHOTS` is set
+pub fn check_snapshot<MSG>(
+    path: impl AsRef<Path>,
+    node: &Node<MSG>,
+) -> Result<(), SnapshotError> {
+    let path = path.as_ref();
+    let actual = normalize(node);
+    if is_updating() {
+        return write_snapshot(path, &actual);
+    }
+    let stored = match fs::read_to_string(path) {
+        Ok(stored) => stored,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Err(SnapshotError::Missing(path.to_path_buf()));
+        }
+        Err(e) => return Err(SnapshotError::Io(path.to_path_buf(), e)),
+    };
+    let expected = stored.replace("\r\n", "\n");
+    let expected = expected.trim_end_matches('\n');
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(SnapshotError::Mismatch {
+            path: path.to_path_buf(),
+            diff: diff(expected, &actual),
+        })
+    }
+}
+
+/// compare the node with the snapshot file and panic when they don't match
+pub fn assert_snapshot<MSG>(path: impl AsRef<Path>, node: &Node<MSG>) {
+    if let Err(e) = check_snapshot(path, node) {
+        panic!("{}", e);
+    }
+}
+
+fn is_updating() -> bool {
+    env::var(UPDATE_SNAPSHOTS)
+        .map(|value| !value.is_empty() && value != "0")
+        .unwrap_or(false)
+}
+
+fn write_snapshot(path: &Path, html: &str) -> Result<(), SnapshotError> {
+    let io_error = |e| SnapshotError::Io(path.to_path_buf(), e);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    fs::write(path, format!("{}\n", html)).map_err(io_error)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Line {
+    Same(usize, usize),
+    Removed(usize),
+    Added(usize),
+}
+
+/// the line diff of the normalized html, where each group of changes
+/// starts with the path of the element containing them, ie: `@@ div > ul > li @@`.
+/// The removed lines start with `-` and the added lines start with `+`.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+    let lines = diff_lines(&old, &new);
+
+    // show the changed lines and the unchanged lines close to them
+    let is_shown = |i: usize| {
+        let start = i.saturating_sub(CONTEXT_LINES);
+        let end = (i + CONTEXT_LINES + 1).min(lines.len());
+        lines[start..end]
+            .iter()
+            .any(|line| !matches!(line, Line::Same(..)))
+    };
+    let mut out = String::new();
+    let mut in_hunk = false;
+    for (i, line) in lines.iter().enumerate() {
+        if !is_shown(i) {
+            in_hunk = false;
+            continue;
+        }
+        if !in_hunk {
+            // the path of the first changed line in this group
+            let changed = lines[i..]
+                .iter()
+                .find(|line| !matches!(line, Line::Same(..)))
+                .expect("a shown line must be close to a changed line");
+            let path = match changed {
+                Line::Same(o, _) | Line::Removed(o) => element_path(&old, *o),
+                Line::Added(n) => element_path(&new, *n),
+            };
+            out.push_str(&format!("@@ {} @@\n", path));
+            in_hunk = true;
+        }
+        match line {
+            Line::Same(o, _) => out.push_str(&format!(" {}\n", old[*o])),
+            Line::Removed(o) => out.push_str(&format!("-{}\n", old[*o])),
+            Line::Added(n) => out.push_str(&format!("+{}\n", new[*n])),
+        }
+    }
+    out
+}
+
+/// the longest common subsequence of the lines
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<Line> {
+    // lcs[i][j] is the length of the common lines of old[i..] and new[j..]
+    let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut lines = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(Line::Same(i, j));
+            i += 1;
+            j += 1;
+        } else if i < old.len()
+            && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1])
+        {
+            lines.push(Line::Removed(i));
+            i += 1;
+        } else {
+            lines.push(Line::Added(j));
+            j += 1;
+        }
+    }
+    lines
+}
+
+/// the tags of the elements containing the line, using the indentation of the lines
+fn element_path(lines: &[&str], index: usize) -> String {
+    let indent_of = |line: &str| line.len() - line.trim_start().len();
+    let mut path = vec![];
+    let mut indent = indent_of(lines[index]);
+    for line in lines[..index].iter().rev() {
+        let line_indent = indent_of(line);
+        if line_indent < indent {
+            indent = line_indent;
+            if let Some(tag) = start_tag_name(line.trim_start()) {
+                path.push(tag);
+            }
+        }
+    }
+    if path.is_empty() {
+        return "root".to_string();
+    }
+    path.reverse();
+    path.join(" > ")
+}
+
+fn start_tag_name(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix('<')?;
+    let end = rest
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .unwrap_or(rest.len());
+    Some(&rest[..end]).filter(|tag| !tag.is_empty())
+}
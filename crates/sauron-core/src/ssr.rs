@@ -0,0 +1,123 @@
+//! This module provides the server-side counterpart of the `Program`.
+//! It drives a `Component` without a browser DOM, so the same component
+//! can be rendered into html in server side application
+//!
+use crate::{
+    initial_state::{render_with_initial_state, InitialStateError},
+    Cmd, Component, Dispatch, Node, Render, RenderOptions,
+};
+use serde::Serialize;
+use std::{cell::RefCell, fmt, marker::PhantomData, rc::Rc};
+
+/// Holds the user App and executes the `init` and `update` of the Component
+/// against the model, the resulting view is then written out using `Render`.
+///
+/// Example:
+/// ```rust,ignore
+/// let program = ServerProgram::new(App::default());
+/// program.dispatch(Msg::Submit(form_data));
+/// let mut buffer = String::new();
+/// program.render(&mut buffer).expect("must render");
+/// ```
+pub struct ServerProgram<APP, MSG>
+where
+    MSG: 'static,
+{
+    /// holds the user application
+    // Note: This needs to be in Rc<RefCell<_>> to allow interior mutability
+    // from a non-mutable reference
+    pub app: Rc<RefCell<APP>>,
+    _phantom_msg: PhantomData<MSG>,
+}
+
+impl<APP, MSG> Clone for ServerProgram<APP, MSG>
+where
+    MSG: 'static,
+{
+    fn clone(&self) -> Self {
+        ServerProgram {
+            app: Rc::clone(&self.app),
+            _phantom_msg: PhantomData,
+        }
+    }
+}
+
+impl<APP, MSG> ServerProgram<APP, MSG>
+where
+    MSG: 'static,
+    APP: Component<MSG> + 'static,
+{
+    /// Create a server program from the app and emit the `Cmd` returned
+    /// from the `init` function of the app.
+    pub fn new(app: APP) -> Self {
+        let program = ServerProgram {
+            app: Rc::new(RefCell::new(app)),
+            _phantom_msg: PhantomData,
+        };
+        let cmds: Cmd<APP, MSG> = program.app.borrow().init();
+        cmds.emit(&program);
+        program
+    }
+
+    /// Create a server program and then dispatch each of the msgs in order.
+    /// This is useful for replaying the user actions, ie: submitted form data
+    /// before rendering the page.
+    pub fn with_msgs(app: APP, msgs: Vec<MSG>) -> Self {
+        let program = Self::new(app);
+        for msg in msgs {
+            program.dispatch(msg);
+        }
+        program
+    }
+
+    /// returns the current view of the app
+    pub fn view(&self) -> Node<MSG> {
+        self.app.borrow().view()
+    }
+
+    /// render the view of the app followed by the serialized app as the initial state,
+    /// which the client side `Program::hydrate_with_initial_state` can start from.
+    pub fn render_with_initial_state(
+        &self,
+        buffer: &mut dyn fmt::Write,
+    ) -> Result<(), InitialStateError>
+    where
+        APP: Serialize,
+    {
+        render_with_initial_state(&self.view(), &*self.app.borrow(), buffer)
+    }
+
+    /// returns the css styles of the app, which can be inserted
+    /// into the `head` of the page being served
+    pub fn style(&self) -> Vec<String> {
+        self.app.borrow().style()
+    }
+}
+
+/// Dispatching a msg on the server is executed right away, since there is no
+/// event loop and no DOM to be updated.
+impl<APP, MSG> Dispatch<MSG> for ServerProgram<APP, MSG>
+where
+    MSG: 'static,
+    APP: Component<MSG> + 'static,
+{
+    fn dispatch(&self, msg: MSG) {
+        let cmd = self.app.borrow_mut().update(msg);
+        cmd.emit(self);
+    }
+}
+
+impl<APP, MSG> Render for ServerProgram<APP, MSG>
+where
+    MSG: 'static,
+    APP: Component<MSG> + 'static,
+{
+    fn render_with_indent(
+        &self,
+        buffer: &mut dyn fmt::Write,
+        options: &RenderOptions,
+        indent: usize,
+    ) -> fmt::Result {
+        self.view().render_with_indent(buffer, options, indent)
+    }
+}
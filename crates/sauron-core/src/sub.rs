@@ -0,0 +1,216 @@
+//! provides functionalities for subscribing to long-lived event sources,
+//! such as window events, intervals and animation frames.
+//!
+use crate::{mt_dom::Callback, Event};
+
+/// Sub is a list of subscriptions to event sources outside of the view.
+/// This is returned at the subscriptions function of a component,
+/// which the Program re-evaluates after each update.
+/// Event sources that are no longer in the returned Sub are removed,
+/// so the subscriptions can be turned on and off depending on the state of the model.
+pub struct Sub<MSG> {
+    pub(crate) subscriptions: Vec<Subscription<MSG>>,
+}
+
+/// A single subscription to an event source
+pub(crate) enum Subscription<MSG> {
+    /// event listener attached to the window,
+    /// the MSG is only emitted when the callback returns Some
+    Window(&'static str, Callback<Event, Option<MSG>>),
+    /// event listener attached to the document
+    Document(&'static str, Callback<Event, Option<MSG>>),
+    /// executed every interval in milliseconds
+    Interval(i32, Callback<(), MSG>),
+    /// executed on every animation frame with the timestamp
+    AnimationFrame(Callback<f64, MSG>),
+}
+
+/// identifies which event source a subscription is listening to
+#[cfg(feature = "with-dom")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SubKey {
+    Window(&'static str),
+    Document(&'static str),
+    Interval(i32),
+    AnimationFrame,
+}
+
+#[cfg(feature = "with-dom")]
+impl<MSG> Subscription<MSG> {
+    pub(crate) fn key(&self) -> SubKey {
+        match self {
+            Subscription::Window(event, _) => SubKey::Window(event),
+            Subscription::Document(event, _) => SubKey::Document(event),
+            Subscription::Interval(millis, _) => SubKey::Interval(*millis),
+            Subscription::AnimationFrame(_) => SubKey::AnimationFrame,
+        }
+    }
+}
+
+impl<MSG> Subscription<MSG>
+where
+    MSG: 'static,
+{
+    fn map_callback<MSG2>(self, cb: Callback<MSG, MSG2>) -> Subscription<MSG2>
+    where
+        MSG2: 'static,
+    {
+        let option_cb = {
+            let cb = cb.clone();
+            Callback::from(move |msg: Option<MSG>| msg.map(|msg| cb.emit(msg)))
+        };
+        match self {
+            Subscription::Window(event, ecb) => {
+                Subscription::Window(event, ecb.map_callback(option_cb))
+            }
+            Subscription::Document(event, ecb) => {
+                Subscription::Document(event, ecb.map_callback(option_cb))
+            }
+            Subscription::Interval(millis, icb) => {
+                Subscription::Interval(millis, icb.map_callback(cb))
+            }
+            Subscription::AnimationFrame(acb) => {
+                Subscription::AnimationFrame(acb.map_callback(cb))
+            }
+        }
+    }
+}
+
+impl<MSG> Sub<MSG>
+where
+    MSG: 'static,
+{
+    /// A Sub with no subscription
+    pub fn none() -> Self {
+        Sub {
+            subscriptions: vec![],
+        }
+    }
+
+    /// creates a unified Sub which batches all the other Subs in one.
+    pub fn batch(subs: Vec<Self>) -> Self {
+        let mut subscriptions = vec![];
+        for sub in subs {
+            subscriptions.extend(sub.subscriptions);
+        }
+        Sub { subscriptions }
+    }
+
+    /// returns true if there is no subscription
+    pub fn is_none(&self) -> bool {
+        self.subscriptions.is_empty()
+    }
+
+    /// listen to the event with `event_name` which is triggered in the window
+    pub fn on_window<F>(event_name: &'static str, f: F) -> Self
+    where
+        F: Fn(Event) -> MSG + 'static,
+    {
+        Self::on_window_filter_map(event_name, move |event| Some(f(event)))
+    }
+
+    /// listen to the event with `event_name` which is triggered in the window,
+    /// the MSG is only emitted when `f` returns Some
+    pub fn on_window_filter_map<F>(event_name: &'static str, f: F) -> Self
+    where
+        F: Fn(Event) -> Option<MSG> + 'static,
+    {
+        Sub {
+            subscriptions: vec![Subscription::Window(
+                event_name,
+                Callback::from(f),
+            )],
+        }
+    }
+
+    /// listen to the event with `event_name` which is triggered in the document
+    pub fn on_document<F>(event_name: &'static str, f: F) -> Self
+    where
+        F: Fn(Event) -> MSG + 'static,
+    {
+        Self::on_document_filter_map(event_name, move |event| Some(f(event)))
+    }
+
+    /// listen to the event with `event_name` which is triggered in the document,
+    /// the MSG is only emitted when `f` returns Some
+    pub fn on_document_filter_map<F>(event_name: &'static str, f: F) -> Self
+    where
+        F: Fn(Event) -> Option<MSG> + 'static,
+    {
+        Sub {
+            subscriptions: vec![Subscription::Document(
+                event_name,
+                Callback::from(f),
+            )],
+        }
+    }
+
+    /// emit the MSG every interval specified in milliseconds
+    pub fn every<F>(millis: i32, f: F) -> Self
+    where
+        F: Fn() -> MSG + 'static,
+    {
+        Sub {
+            subscriptions: vec![Subscription::Interval(
+                millis,
+                Callback::from(move |_| f()),
+            )],
+        }
+    }
+
+    /// emit the MSG on every animation frame,
+    /// the function is supplied with the timestamp of the frame
+    pub fn on_animation_frame<F>(f: F) -> Self
+    where
+        F: Fn(f64) -> MSG + 'static,
+    {
+        Sub {
+            subscriptions: vec![Subscription::AnimationFrame(Callback::from(
+                f,
+            ))],
+        }
+    }
+
+    /// map the Sub of a sub component, such that it can be
+    /// used in the subscriptions of the parent component.
+    pub fn map_msg<F, MSG2>(self, func: F) -> Sub<MSG2>
+    where
+        F: Fn(MSG) -> MSG2 + 'static,
+        MSG2: 'static,
+    {
+        let cb = Callback::from(func);
+        Sub {
+            subscriptions: self
+                .subscriptions
+                .into_iter()
+                .map(|sub| sub.map_callback(cb.clone()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "with-dom")]
+impl<MSG> Sub<MSG>
+where
+    MSG: 'static,
+{
+    /// emit the MSG whenever the browser is resized, supplied with the
+    /// new width and height of the window
+    pub fn on_resize<F>(f: F) -> Self
+    where
+        F: Fn(i32, i32) -> MSG + 'static,
+    {
+        Self::on_window("resize", move |_| {
+            let (window_width, window_height) = crate::Browser::get_size();
+            f(window_width, window_height)
+        })
+    }
+
+    /// emit the MSG whenever the hash portion of the window location url is changed
+    pub fn on_hashchange<F>(f: F) -> Self
+    where
+        F: Fn(String) -> MSG + 'static,
+    {
+        Self::on_window("hashchange", move |_| f(crate::Browser::get_hash()))
+    }
+}
@@ -0,0 +1,351 @@
+//! Provides a harness for testing a `Component` natively with `cargo test`, without a browser.
+//!
+//! The harness owns the component, feeds it with msgs and keeps the `Cmd`s returned
+//! from `init` and `update`, so the test can check them and decide when to run them.
+//! The latest view can be queried and the events are simulated by calling
+//! the callbacks of the event listeners in the view.
+//! Without the `with-dom` feature, the events passed to the listeners are the
+//! synthetic events from `html::events`, such as `MouseEvent::new(10, 20)`.
+//!
+//! Example:
+//! ```rust
+//! use sauron_core::{
+//!     html::{attributes::*, *},
+//!     test_harness::TestHarness,
+//!     Cmd, Component, Node,
+//! };
+//!
+//! struct Counter(i32);
+//!
+//! impl Component<i32> for Counter {
+//!     fn update(&mut self, msg: i32) -> Cmd<Self, i32> {
+//!         self.0 += msg;
+//!         Cmd::none()
+//!     }
+//!
+//!     fn view(&self) -> Node<i32> {
+//!         div(vec![], vec![span(vec![id("count")], vec![text(self.0)])])
+//!     }
+//! }
+//!
+//! let harness = TestHarness::new(Counter(0));
+//! harness.send(2);
+//! harness.send(3);
+//! assert_eq!(harness.text_by_id("count"), Some("5".to_string()));
+//! ```
+use crate::{
+    html::events::event_filters, Cmd, Component, Dispatch, Event, Node,
+    QuerySelector,
+};
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
+
+/// Owns a component and drives it the way the `Program` does,
+/// except that the `Cmd`s are captured instead of being executed.
+pub struct TestHarness<APP, MSG>
+where
+    MSG: 'static,
+{
+    app: Rc<RefCell<APP>>,
+    /// the view of the app after the last update
+    view: Rc<RefCell<Node<MSG>>>,
+    /// the cmds returned from init and update which are not run yet
+    cmds: Rc<RefCell<Vec<Cmd<APP, MSG>>>>,
+}
+
+impl<APP, MSG> Clone for TestHarness<APP, MSG>
+where
+    MSG: 'static,
+{
+    fn clone(&self) -> Self {
+        TestHarness {
+            app: Rc::clone(&self.app),
+            view: Rc::clone(&self.view),
+            cmds: Rc::clone(&self.cmds),
+        }
+    }
+}
+
+impl<APP, MSG> TestHarness<APP, MSG>
+where
+    MSG: 'static,
+    APP: Component<MSG> + 'static,
+{
+    /// create a harness for the app, the Cmd from `init` is captured
+    pub fn new(app: APP) -> Self {
+        let view = app.view();
+        let harness = TestHarness {
+            app: Rc::new(RefCell::new(app)),
+            view: Rc::new(RefCell::new(view)),
+            cmds: Rc::new(RefCell::new(vec![])),
+        };
+        let cmd = harness.app.borrow().init();
+        harness.capture(cmd);
+        harness
+    }
+
+    /// the app owned by this harness
+    pub fn app(&self) -> Ref<'_, APP> {
+        self.app.borrow()
+    }
+
+    /// update the app with the msg, the returned Cmd is captured
+    /// and the view is created again
+    pub fn send(&self, msg: MSG) {
+        let cmd = self.app.borrow_mut().update(msg);
+        self.capture(cmd);
+        let view = self.app.borrow().view();
+        *self.view.borrow_mut() = view;
+    }
+
+    /// update the app with each of the msgs in order
+    pub fn send_all(&self, msgs: Vec<MSG>) {
+        for msg in msgs {
+            self.send(msg);
+        }
+    }
+
+    /// the number of captured Cmds which are not run yet
+    pub fn cmd_count(&self) -> usize {
+        self.cmds.borrow().len()
+    }
+
+    /// take the captured Cmds out of this harness, so they can be inspected
+    /// or executed with another dispatcher
+    pub fn take_cmds(&self) -> Vec<Cmd<APP, MSG>> {
+        self.cmds.borrow_mut().drain(..).collect()
+    }
+
+    /// run the captured Cmds with this harness as the dispatcher,
+    /// the msgs dispatched from them are sent to the app right away.
+    /// The Cmds returned from those updates are captured and not run.
+    pub fn run_cmds(&self) {
+        for cmd in self.take_cmds() {
+            cmd.emit(self);
+        }
+    }
+
+    /// the view of the app after the last update
+    pub fn view(&self) -> Ref<'_, Node<MSG>> {
+        self.view.borrow()
+    }
+
+    /// returns true if the view has an element with this id
+    pub fn has_id(&self, id: &str) -> bool {
+        find_by_id(&self.view(), id).is_some()
+    }
+
+    /// the text content of the element with this id in the view
+    pub fn text_by_id(&self, id: &str) -> Option<String> {
+        find_by_id(&self.view(), id).map(text_content)
+    }
+
+    /// the text content of each of the elements with this class in the view
+    pub fn texts_by_class(&self, class: &str) -> Vec<String> {
+        find_all_by_class(&self.view(), class)
+            .into_iter()
+            .map(text_content)
+            .collect()
+    }
+
+    /// call the listeners for `event_name` of the first element matching the selector
+    /// and send the resulting msgs to the app.
+    /// The event names are the ones used in `on`, ie: `click`, `input`.
+    ///
+    /// # Panics
+    /// when no element matches the selector or it has no listener for the event
+    pub fn trigger(
+        &self,
+        selector: &str,
+        event_name: &str,
+        event: impl Into<Event>,
+    ) {
+        let msgs = {
+            let view = self.view();
+            let node = find_first(&view, selector);
+            let msgs = emit_event(node, event_name, event.into());
+            if msgs.is_empty() {
+                panic!(
+                    "element {:?} has no listener for {:?}",
+                    selector, event_name
+                );
+            }
+            msgs
+        };
+        self.send_all(msgs);
+    }
+
+    fn capture(&self, cmd: Cmd<APP, MSG>) {
+        if !cmd.is_none() {
+            self.cmds.borrow_mut().push(cmd);
+        }
+    }
+}
+
+/// Dispatching on the harness updates the app right away, the same way as `send`
+impl<APP, MSG> Dispatch<MSG> for TestHarness<APP, MSG>
+where
+    MSG: 'static,
+    APP: Component<MSG> + 'static,
+{
+    fn dispatch(&self, msg: MSG) {
+        self.send(msg)
+    }
+}
+
+pub use crate::query::attribute_value;
+
+/// call the listeners for `event_name` of the node and return the msgs they produce,
+/// the listeners are not called when the event is not accepted by their filters
+pub fn emit_event<MSG>(
+    node: &Node<MSG>,
+    event_name: &str,
+    event: Event,
+) -> Vec<MSG>
+where
+    MSG: 'static,
+{
+    let attrs: Vec<_> = node
+        .get_attributes()
+        .unwrap_or_default()
+        .iter()
+        .filter(|attr| *attr.name() == event_name)
+        .collect();
+    mt_dom::merge_attributes_of_same_name(&attrs)
+        .iter()
+        .filter(|attr| {
+            event_filters(attr)
+                .iter()
+                .all(|filter| filter.accepts(&event))
+        })
+        .flat_map(|attr| attr.get_callback())
+        .map(|cb| cb.emit(event.clone()))
+        .collect()
+}
+
+/// call the listener for the event of the first element matching the selector
+/// and return the msg it produces.
+/// The event name is taken from the event, ie: `click` for a `MouseEvent`,
+/// use `emit_event` to call the listeners of other event names.
+/// See the `query` module for the supported selectors.
+///
+/// # Panics
+/// when no element matches the selector or none of its listeners accepts the event
+pub fn trigger<MSG>(
+    node: &Node<MSG>,
+    selector: &str,
+    event: impl Into<Event>,
+) -> MSG
+where
+    MSG: 'static,
+{
+    let event = event.into();
+    let target = find_first(node, selector);
+    let names = event_names(&event);
+    names
+        .iter()
+        .find_map(|name| emit_event(target, name, event.clone()).pop())
+        .unwrap_or_else(|| {
+            panic!("element {:?} has no listener for {:?}", selector, names)
+        })
+}
+
+/// the names of the events which the synthetic event can be dispatched as,
+/// in the order they are tried
+#[cfg(not(feature = "with-dom"))]
+fn event_names(event: &Event) -> Vec<&'static str> {
+    use crate::html::events::SyntheticEvent;
+    match event {
+        SyntheticEvent::Mouse(_) => vec![
+            "click",
+            "dblclick",
+            "mousedown",
+            "mouseup",
+            "mousemove",
+            "mouseover",
+            "mouseout",
+            "mouseenter",
+            "mouseleave",
+            "contextmenu",
+            "auxclick",
+        ],
+        SyntheticEvent::Wheel(_) => vec!["wheel"],
+        SyntheticEvent::Keyboard(_) => vec!["keydown", "keypress", "keyup"],
+        SyntheticEvent::Input(_) => vec!["input", "change"],
+        SyntheticEvent::Scroll(..) => vec!["scroll"],
+        SyntheticEvent::Animation(_) => vec!["animationend"],
+        SyntheticEvent::Transition(_) => vec!["transitionend"],
+        SyntheticEvent::HashChange(_) => vec!["hashchange"],
+        SyntheticEvent::Pointer(_) => vec![
+            "pointerdown",
+            "pointerup",
+            "pointermove",
+            "pointerover",
+            "pointerout",
+            "pointerenter",
+            "pointerleave",
+            "pointercancel",
+        ],
+        SyntheticEvent::Touch(_) => {
+            vec!["touchstart", "touchmove", "touchend", "touchcancel"]
+        }
+        SyntheticEvent::Drag(_) => vec![
+            "dragstart",
+            "drag",
+            "dragenter",
+            "dragover",
+            "dragleave",
+            "drop",
+            "dragend",
+        ],
+        SyntheticEvent::Focus(_) => {
+            vec!["focus", "blur", "focusin", "focusout"]
+        }
+        SyntheticEvent::Clipboard(_) => vec!["copy", "cut", "paste"],
+        SyntheticEvent::Composition(_) => {
+            vec!["compositionstart", "compositionupdate", "compositionend"]
+        }
+        SyntheticEvent::Plain => vec!["submit", "reset", "select"],
+    }
+}
+
+/// the browser event carries its own event name
+#[cfg(feature = "with-dom")]
+fn event_names(event: &Event) -> Vec<String> {
+    vec![event.type_()]
+}
+
+/// the first node matching the selector, panics if there is none
+fn find_first<'a, MSG>(node: &'a Node<MSG>, selector: &str) -> &'a Node<MSG> {
+    node.query_selector(selector).unwrap_or_else(|| {
+        panic!("no element matching {:?} in the view", selector)
+    })
+}
+
+/// find the first node in the tree with this id, including the node itself
+pub fn find_by_id<'a, MSG>(
+    node: &'a Node<MSG>,
+    id: &str,
+) -> Option<&'a Node<MSG>> {
+    node.query_selector(&format!("#{}", id))
+}
+
+/// find all the nodes in the tree which has this class, in document order
+pub fn find_all_by_class<'a, MSG>(
+    node: &'a Node<MSG>,
+    class: &str,
+) -> Vec<&'a Node<MSG>> {
+    node.query_selector_all(&format!(".{}", class))
+}
+
+/// the text of the node and all its descendants
+pub fn text_content<MSG>(node: &Node<MSG>) -> String {
+    match node {
+        Node::Text(text) => text.to_string(),
+        Node::Element(element) => {
+            element.get_children().iter().map(text_content).collect()
+        }
+    }
+}
@@ -0,0 +1,59 @@
+#![deny(warnings)]
+use sauron_core::{Cmd, Dispatch};
+use std::{cell::RefCell, rc::Rc};
+
+#[derive(Debug, PartialEq)]
+enum ChildMsg {
+    Loaded(u32),
+}
+
+#[derive(Debug, PartialEq)]
+enum Msg {
+    Child(usize, ChildMsg),
+    Done,
+}
+
+struct Child;
+struct Parent;
+
+/// a dispatcher which just records the dispatched msgs
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<Msg>>>);
+
+impl Dispatch<Msg> for Recorder {
+    fn dispatch(&self, msg: Msg) {
+        self.0.borrow_mut().push(msg);
+    }
+}
+
+#[test]
+fn child_cmd_is_mapped_into_parent_msg() {
+    let child_cmd: Cmd<Child, ChildMsg> = Cmd::batch(vec![
+        Cmd::new(|program| program.dispatch(ChildMsg::Loaded(1))),
+        Cmd::new(|program| program.dispatch(ChildMsg::Loaded(2))),
+    ]);
+    let index = 3;
+    let parent_cmd: Cmd<Parent, Msg> = Cmd::batch(vec![
+        child_cmd.map_msg(move |child_msg| Msg::Child(index, child_msg)),
+        Cmd::new(|program| program.dispatch(Msg::Done)),
+    ]);
+
+    let recorder = Recorder::default();
+    parent_cmd.emit(&recorder);
+    assert_eq!(
+        *recorder.0.borrow(),
+        vec![
+            Msg::Child(3, ChildMsg::Loaded(1)),
+            Msg::Child(3, ChildMsg::Loaded(2)),
+            Msg::Done
+        ]
+    );
+}
+
+#[test]
+fn none_cmd_maps_to_none() {
+    let child_cmd: Cmd<Child, ChildMsg> = Cmd::none();
+    let parent_cmd: Cmd<Parent, Msg> =
+        child_cmd.map_msg(|child_msg| Msg::Child(0, child_msg));
+    assert!(parent_cmd.is_none());
+}
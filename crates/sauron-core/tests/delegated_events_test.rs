@@ -0,0 +1,205 @@
+#![deny(warnings)]
+use sauron_core::{
+    html::{attributes::*, events::*, *},
+    DomUpdater, Node,
+};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn delegated_mount(view: Node<()>) -> DomUpdater<()> {
+    let mut dom_updater = DomUpdater::new(view, &sauron_core::body());
+    dom_updater.delegate_events();
+    dom_updater.append_to_mount(&simple_program());
+    dom_updater
+}
+
+fn dispatch(elem_id: &str, event_name: &str, bubbles: bool) -> web_sys::Event {
+    let init = web_sys::EventInit::new();
+    init.set_bubbles(bubbles);
+    init.set_cancelable(true);
+    let event =
+        web_sys::Event::new_with_event_init_dict(event_name, &init).unwrap();
+    let element = sauron_core::document().get_element_by_id(elem_id).unwrap();
+    web_sys::EventTarget::from(element)
+        .dispatch_event(&event)
+        .unwrap();
+    event
+}
+
+/// the listeners which are called, in order
+type Calls = Rc<RefCell<Vec<&'static str>>>;
+
+#[wasm_bindgen_test]
+fn events_bubble_through_the_view_without_closures_per_element() {
+    let calls: Calls = Rc::default();
+    let calls_clone = Rc::clone(&calls);
+    let calls_clone2 = Rc::clone(&calls);
+    let view: Node<()> = div(
+        vec![
+            id("delegated-parent"),
+            on_click(move |_| calls_clone.borrow_mut().push("parent")),
+        ],
+        vec![
+            text("a"),
+            text("b"),
+            button(
+                vec![
+                    id("delegated-button"),
+                    on_click(move |_| calls_clone2.borrow_mut().push("button")),
+                ],
+                vec![text("click")],
+            ),
+        ],
+    );
+    let dom_updater = delegated_mount(view);
+    assert_eq!(dom_updater.active_closure_len(), 0);
+    assert_eq!(dom_updater.delegated_listener_len(), 1);
+
+    let parent = sauron_core::document()
+        .get_element_by_id("delegated-parent")
+        .unwrap();
+    assert!(!parent.outer_html().contains("data-sauron-vdom-id"));
+
+    dispatch("delegated-button", "click", true);
+    assert_eq!(*calls.borrow(), vec!["button", "parent"]);
+}
+
+#[wasm_bindgen_test]
+fn delegated_listener_options_are_applied() {
+    let calls: Calls = Rc::default();
+    let calls_clone = Rc::clone(&calls);
+    let calls_clone2 = Rc::clone(&calls);
+    let view: Node<()> = div(
+        vec![on_click(move |_| calls_clone.borrow_mut().push("parent"))],
+        vec![a(
+            vec![
+                id("delegated-link"),
+                on_click(move |_| calls_clone2.borrow_mut().push("link"))
+                    .prevent_default()
+                    .stop_propagation(),
+            ],
+            vec![],
+        )],
+    );
+    let _dom_updater = delegated_mount(view);
+
+    let click = dispatch("delegated-link", "click", true);
+    assert!(click.default_prevented());
+    assert_eq!(*calls.borrow(), vec!["link"]);
+}
+
+#[wasm_bindgen_test]
+fn non_bubbling_events_are_only_called_in_the_target() {
+    let calls: Calls = Rc::default();
+    let calls_clone = Rc::clone(&calls);
+    let calls_clone2 = Rc::clone(&calls);
+    let view: Node<()> = div(
+        vec![on_focus(move |_| calls_clone.borrow_mut().push("parent"))],
+        vec![input(
+            vec![
+                id("delegated-input"),
+                on_focus(move |_| calls_clone2.borrow_mut().push("input")),
+            ],
+            vec![],
+        )],
+    );
+    let _dom_updater = delegated_mount(view);
+
+    dispatch("delegated-input", "focus", false);
+    assert_eq!(*calls.borrow(), vec!["input"]);
+}
+
+#[wasm_bindgen_test]
+fn listeners_are_looked_up_in_the_updated_view() {
+    let calls: Calls = Rc::default();
+    let calls_clone = Rc::clone(&calls);
+    let calls_clone2 = Rc::clone(&calls);
+    let old: Node<()> = div(
+        vec![],
+        vec![button(
+            vec![
+                id("delegated-update"),
+                on_click(move |_| calls_clone.borrow_mut().push("old")),
+            ],
+            vec![],
+        )],
+    );
+    let new: Node<()> = div(
+        vec![],
+        vec![
+            p(vec![], vec![text("inserted")]),
+            button(
+                vec![
+                    id("delegated-update"),
+                    on_mousedown(move |_| {
+                        calls_clone2.borrow_mut().push("new")
+                    }),
+                ],
+                vec![],
+            ),
+        ],
+    );
+    let mut dom_updater = delegated_mount(old);
+    dom_updater.update_dom(&simple_program(), new);
+    assert_eq!(dom_updater.active_closure_len(), 0);
+    assert_eq!(dom_updater.delegated_listener_len(), 2);
+
+    dispatch("delegated-update", "click", true);
+    dispatch("delegated-update", "mousedown", true);
+    assert_eq!(*calls.borrow(), vec!["new"]);
+}
+
+#[wasm_bindgen_test]
+fn mapped_delegated_updater_listens_again_in_the_next_update() {
+    let calls: Calls = Rc::default();
+    let calls_clone = Rc::clone(&calls);
+    let view = |calls: Calls| -> Node<()> {
+        div(
+            vec![],
+            vec![button(
+                vec![
+                    id("delegated-mapped"),
+                    on_click(move |_| calls.borrow_mut().push("mapped")),
+                ],
+                vec![],
+            )],
+        )
+    };
+    let dom_updater = delegated_mount(view(Rc::clone(&calls)));
+    assert_eq!(dom_updater.delegated_listener_len(), 1);
+
+    let mut mapped: DomUpdater<()> = dom_updater.map_msg(|msg| msg);
+    assert!(mapped.is_delegating_events());
+    assert_eq!(mapped.delegated_listener_len(), 0);
+
+    mapped.update_dom(&simple_program(), view(calls_clone));
+    assert_eq!(mapped.delegated_listener_len(), 1);
+    dispatch("delegated-mapped", "click", true);
+    assert_eq!(*calls.borrow(), vec!["mapped"]);
+}
+
+#[wasm_bindgen_test]
+fn delegated_once_listener_is_called_once() {
+    let calls: Calls = Rc::default();
+    let calls_clone = Rc::clone(&calls);
+    let view: Node<()> = div(
+        vec![],
+        vec![button(
+            vec![
+                id("delegated-once"),
+                on_click(move |_| calls_clone.borrow_mut().push("once")).once(),
+            ],
+            vec![],
+        )],
+    );
+    let _dom_updater = delegated_mount(view);
+
+    dispatch("delegated-once", "click", true);
+    dispatch("delegated-once", "click", true);
+    assert_eq!(*calls.borrow(), vec!["once"]);
+}
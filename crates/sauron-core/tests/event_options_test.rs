@@ -0,0 +1,61 @@
+#![deny(warnings)]
+use sauron_core::{
+    html::{
+        attributes::{class, AttributeValue},
+        events::*,
+        *,
+    },
+    Attribute, Node, Render,
+};
+
+fn options<MSG>(attr: &Attribute<MSG>) -> Vec<EventOptions> {
+    attr.get_plain()
+        .into_iter()
+        .filter_map(|value| match value {
+            AttributeValue::EventOptions(options) => Some(*options),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn options_are_kept_with_the_callback() {
+    let attr: Attribute<()> = on_click(|_| ()).prevent_default().once();
+    assert_eq!("click", *attr.name());
+    assert_eq!(1, attr.get_callback().len());
+    assert_eq!(
+        vec![
+            EventOptions {
+                prevent_default: true,
+                ..Default::default()
+            },
+            EventOptions {
+                once: true,
+                ..Default::default()
+            },
+        ],
+        options(&attr)
+    );
+}
+
+#[test]
+fn options_given_to_the_builder() {
+    let capture = EventOptions {
+        capture: true,
+        passive: true,
+        ..Default::default()
+    };
+    let attr: Attribute<()> = on_with_options("scroll", capture, |_| ());
+    assert_eq!(vec![capture], options(&attr));
+}
+
+#[test]
+fn options_are_not_rendered() {
+    let node: Node<()> = button(
+        vec![class("btn"), on_click(|_| ()).stop_propagation()],
+        vec![],
+    );
+    let mut buffer = String::new();
+    node.render_compact(&mut buffer).unwrap();
+    assert_eq!("<button class=\"btn\"></button>", buffer);
+}
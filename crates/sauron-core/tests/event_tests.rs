@@ -8,11 +8,13 @@ use sauron_core::{
     diff,
     dom::DomUpdater,
     html::{attributes::*, events::*, *},
+    router::{Route, Router, Url},
     Node, Patch,
 };
 use std::cell::RefCell;
 use std::rc::Rc;
 use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
 use wasm_bindgen_test::*;
 
 mod test_fixtures;
@@ -274,3 +276,213 @@ fn remove_event_from_replaced_node() {
         "There should only be 0 left after replacing it with a different tag"
     );
 }
+
+#[wasm_bindgen_test]
+fn listener_options_are_applied() {
+    let clicks = Rc::new(RefCell::new(0));
+    let clicks_clone = Rc::clone(&clicks);
+    let parent_clicks = Rc::new(RefCell::new(0));
+    let parent_clicks_clone = Rc::clone(&parent_clicks);
+
+    let elem_id = "button-listener-options-test";
+    let node: Node<()> = div(
+        vec![on_click(move |_| *parent_clicks_clone.borrow_mut() += 1)],
+        vec![button(
+            vec![
+                id(elem_id),
+                on_click(move |_| *clicks_clone.borrow_mut() += 1)
+                    .prevent_default()
+                    .stop_propagation()
+                    .once(),
+            ],
+            vec![],
+        )],
+    );
+
+    let body = sauron_core::body();
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, node, &body);
+    let button = sauron_core::document().get_element_by_id(elem_id).unwrap();
+
+    let init = web_sys::EventInit::new();
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    let click =
+        web_sys::Event::new_with_event_init_dict("click", &init).unwrap();
+    web_sys::EventTarget::from(button.clone())
+        .dispatch_event(&click)
+        .unwrap();
+    assert!(click.default_prevented());
+    assert_eq!(*clicks.borrow(), 1);
+    assert_eq!(*parent_clicks.borrow(), 0);
+
+    // the listener is removed after the first click
+    let click =
+        web_sys::Event::new_with_event_init_dict("click", &init).unwrap();
+    web_sys::EventTarget::from(button)
+        .dispatch_event(&click)
+        .unwrap();
+    assert_eq!(*clicks.borrow(), 1);
+    assert_eq!(*parent_clicks.borrow(), 1);
+}
+
+#[wasm_bindgen_test]
+fn pointer_event_is_mapped() {
+    let pointer = Rc::new(RefCell::new((0, String::new())));
+    let pointer_clone = Rc::clone(&pointer);
+
+    let elem_id = "pointer-event-test";
+    let node: Node<()> = div(
+        vec![
+            id(elem_id),
+            on_pointerdown(move |event: PointerEvent| {
+                *pointer_clone.borrow_mut() =
+                    (event.pointer_id(), event.pointer_type());
+            }),
+        ],
+        vec![],
+    );
+
+    let body = sauron_core::body();
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, node, &body);
+    let element = sauron_core::document().get_element_by_id(elem_id).unwrap();
+
+    let init = web_sys::PointerEventInit::new();
+    init.set_pointer_id(5);
+    init.set_pointer_type("pen");
+    let pointerdown =
+        web_sys::PointerEvent::new_with_event_init_dict("pointerdown", &init)
+            .unwrap();
+    web_sys::EventTarget::from(element)
+        .dispatch_event(&pointerdown)
+        .unwrap();
+    assert_eq!(*pointer.borrow(), (5, "pen".to_string()));
+}
+
+#[wasm_bindgen_test]
+fn select_values_are_mapped_and_patched() {
+    let selected = Rc::new(RefCell::new(vec![]));
+    let selected_clone = Rc::clone(&selected);
+
+    let elem_id = "select-values-test";
+    let view = |value: &'static str| -> Node<()> {
+        let selected_clone = Rc::clone(&selected_clone);
+        select(
+            vec![
+                id(elem_id),
+                attr("value", value),
+                on_change(move |event: InputEvent| {
+                    *selected_clone.borrow_mut() = event.selected;
+                }),
+            ],
+            vec![
+                option(vec![attr("value", "a")], vec![text("Apple")]),
+                option(vec![attr("value", "b")], vec![text("Banana")]),
+            ],
+        )
+    };
+
+    let body = sauron_core::body();
+    let simple_program = simple_program();
+    let mut dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, view("b"), &body);
+    let element = sauron_core::document().get_element_by_id(elem_id).unwrap();
+    let select: &web_sys::HtmlSelectElement = element.unchecked_ref();
+    assert_eq!(select.value(), "b");
+
+    dom_updater.update_dom(&simple_program, view("a"));
+    assert_eq!(select.value(), "a");
+
+    let change = web_sys::Event::new("change").unwrap();
+    web_sys::EventTarget::from(element)
+        .dispatch_event(&change)
+        .unwrap();
+    assert_eq!(*selected.borrow(), vec!["a".to_string()]);
+}
+
+#[wasm_bindgen_test]
+fn input_event_of_contenteditable_does_not_panic() {
+    let content = Rc::new(RefCell::new(String::new()));
+    let content_clone = Rc::clone(&content);
+
+    let elem_id = "contenteditable-test";
+    let node: Node<()> = div(
+        vec![
+            id(elem_id),
+            attr("contenteditable", "true"),
+            on_input(move |event: InputEvent| {
+                *content_clone.borrow_mut() = event.value;
+            }),
+        ],
+        vec![text("edited")],
+    );
+
+    let body = sauron_core::body();
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, node, &body);
+    let element = sauron_core::document().get_element_by_id(elem_id).unwrap();
+
+    let input_event = web_sys::InputEvent::new("input").unwrap();
+    web_sys::EventTarget::from(element)
+        .dispatch_event(&input_event)
+        .unwrap();
+    assert_eq!(*content.borrow(), "edited");
+}
+
+#[derive(Clone)]
+struct Section;
+
+impl Route for Section {
+    fn from_url(_url: &Url) -> Option<Self> {
+        Some(Section)
+    }
+
+    fn to_url(&self) -> String {
+        "#section".to_string()
+    }
+}
+
+#[wasm_bindgen_test]
+fn link_leaves_modified_clicks_to_the_browser() {
+    let navigated = Rc::new(RefCell::new(0));
+    let navigated_clone = Rc::clone(&navigated);
+
+    let elem_id = "router-link-test";
+    let node: Node<()> = Router::link(
+        &Section,
+        move |_| *navigated_clone.borrow_mut() += 1,
+        vec![id(elem_id)],
+        vec![text("section")],
+    );
+
+    let body = sauron_core::body();
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, node, &body);
+    let element = sauron_core::document().get_element_by_id(elem_id).unwrap();
+
+    // returns false if the default action of the click is prevented
+    let click = |init: web_sys::MouseEventInit| {
+        init.set_bubbles(true);
+        init.set_cancelable(true);
+        let event =
+            web_sys::MouseEvent::new_with_mouse_event_init_dict("click", &init)
+                .unwrap();
+        element.dispatch_event(&event).unwrap()
+    };
+
+    let ctrl_click = web_sys::MouseEventInit::new();
+    ctrl_click.set_ctrl_key(true);
+    assert!(click(ctrl_click));
+    let middle_click = web_sys::MouseEventInit::new();
+    middle_click.set_button(1);
+    assert!(click(middle_click));
+    assert_eq!(*navigated.borrow(), 0);
+
+    assert!(!click(web_sys::MouseEventInit::new()));
+    assert_eq!(*navigated.borrow(), 1);
+}
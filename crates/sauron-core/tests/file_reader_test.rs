@@ -0,0 +1,115 @@
+#![deny(warnings)]
+#![cfg(feature = "with-dom")]
+use sauron_core::{
+    dom::file_reader::{FileError, FileReader, Progress},
+    html::div,
+    Cmd, Component, Dispatch, File, Node,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn progress_fraction() {
+    let progress = Progress {
+        loaded: 256,
+        total: Some(1024),
+    };
+    assert_eq!(progress.fraction(), Some(0.25));
+    let empty_file = Progress {
+        loaded: 0,
+        total: Some(0),
+    };
+    assert_eq!(empty_file.fraction(), Some(1.0));
+    let unknown_size = Progress {
+        loaded: 100,
+        total: None,
+    };
+    assert_eq!(unknown_size.fraction(), None);
+}
+
+#[derive(Debug, PartialEq)]
+enum Msg {
+    Text(Result<String, FileError>),
+    Bytes(Result<Vec<u8>, FileError>),
+    DataUrl(Result<String, FileError>),
+}
+
+struct App;
+
+impl Component<Msg> for App {
+    fn update(&mut self, _msg: Msg) -> Cmd<Self, Msg> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(vec![], vec![])
+    }
+}
+
+/// keeps the dispatched msg and resolves the promise with it
+#[derive(Clone)]
+struct Recorder {
+    msg: Rc<RefCell<Option<Msg>>>,
+    resolve: js_sys::Function,
+}
+
+impl Dispatch<Msg> for Recorder {
+    fn dispatch(&self, msg: Msg) {
+        *self.msg.borrow_mut() = Some(msg);
+        self.resolve
+            .call0(&wasm_bindgen::JsValue::NULL)
+            .expect("must resolve");
+    }
+}
+
+/// emit the cmd and wait for the msg it dispatches
+async fn dispatched(cmd: Cmd<App, Msg>) -> Msg {
+    let msg = Rc::new(RefCell::new(None));
+    let mut resolve = None;
+    let promise = js_sys::Promise::new(&mut |res, _rej| resolve = Some(res));
+    let recorder = Recorder {
+        msg: Rc::clone(&msg),
+        resolve: resolve.expect("must have resolve"),
+    };
+    cmd.emit(&recorder);
+    JsFuture::from(promise).await.expect("must be resolved");
+    let msg = msg.borrow_mut().take();
+    msg.expect("must dispatch a msg")
+}
+
+fn hello_file() -> File {
+    let parts = js_sys::Array::of1(&"hello".into());
+    let blob =
+        web_sys::Blob::new_with_str_sequence(&parts).expect("must create blob");
+    let file = web_sys::File::new_with_blob_sequence(
+        &js_sys::Array::of1(&blob),
+        "hello.txt",
+    )
+    .expect("must create file");
+    File::from(file)
+}
+
+#[wasm_bindgen_test]
+async fn file_content_is_read() {
+    let file = hello_file();
+    assert_eq!(file.name(), "hello.txt");
+    assert_eq!(file.size(), 5);
+
+    assert_eq!(
+        dispatched(FileReader::read(&file).text(Msg::Text)).await,
+        Msg::Text(Ok("hello".to_string()))
+    );
+    assert_eq!(
+        dispatched(FileReader::read(&file).bytes(Msg::Bytes)).await,
+        Msg::Bytes(Ok(b"hello".to_vec()))
+    );
+    assert_eq!(
+        dispatched(FileReader::read(&file).data_url(Msg::DataUrl)).await,
+        Msg::DataUrl(Ok(
+            "data:application/octet-stream;base64,aGVsbG8=".to_string()
+        ))
+    );
+}
@@ -0,0 +1,57 @@
+#![deny(warnings)]
+#![cfg(feature = "with-dom")]
+use sauron_core::dom::http::{HttpError, Response};
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct User {
+    id: u32,
+    name: String,
+}
+
+fn response(status: u16, body: &str) -> Response {
+    Response {
+        url: "/api/user/1".to_string(),
+        status,
+        status_text: String::new(),
+        headers: vec![(
+            "content-type".to_string(),
+            "application/json".to_string(),
+        )],
+        body: body.as_bytes().to_vec(),
+    }
+}
+
+#[test]
+fn decode_json_body() {
+    let response = response(200, r#"{"id": 1, "name": "Alice"}"#);
+    assert!(response.is_ok());
+    assert_eq!(response.header("Content-Type"), Some("application/json"));
+    assert_eq!(
+        response.json::<User>(),
+        Ok(User {
+            id: 1,
+            name: "Alice".to_string()
+        })
+    );
+}
+
+#[test]
+fn non_2xx_status_is_an_error() {
+    assert!(response(204, "").error_for_status().is_ok());
+    let not_found = response(404, "not found");
+    assert_eq!(
+        not_found.clone().error_for_status(),
+        Err(HttpError::Status(Box::new(not_found)))
+    );
+}
+
+#[test]
+fn invalid_body() {
+    let response = Response {
+        body: vec![0xff, 0xfe],
+        ..response(200, "")
+    };
+    assert!(matches!(response.text(), Err(HttpError::Utf8(_))));
+    assert!(matches!(response.json::<User>(), Err(HttpError::Json(_))));
+}
@@ -0,0 +1,99 @@
+#![deny(warnings)]
+use sauron_core::{
+    html::{attributes::*, events::*, *},
+    DomUpdater, Node, Render,
+};
+use std::{cell::RefCell, rc::Rc};
+use test_fixtures::simple_program;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// render the view into a container and return the rendered root element
+fn server_render(container_id: &str, vdom: &Node<()>) -> web_sys::Element {
+    let document = sauron_core::document();
+    let container = document.create_element("div").unwrap();
+    container.set_id(container_id);
+    let mut buffer = String::new();
+    vdom.render(&mut buffer).unwrap();
+    container.set_inner_html(&buffer);
+    sauron_core::body().append_child(&container).unwrap();
+    container.first_element_child().unwrap()
+}
+
+#[wasm_bindgen_test]
+fn hydrate_reuses_existing_elements_and_attach_events() {
+    console_error_panic_hook::set_once();
+    let clicked = Rc::new(RefCell::new(false));
+    let clicked_clone = Rc::clone(&clicked);
+
+    let view = |clicked: Rc<RefCell<bool>>| -> Node<()> {
+        div(
+            vec![class("app")],
+            vec![
+                p(vec![], vec![text("hello")]),
+                button(
+                    vec![
+                        id("hydrate-button"),
+                        on_click(move |_| *clicked.borrow_mut() = true),
+                    ],
+                    vec![text("Click me")],
+                ),
+            ],
+        )
+    };
+
+    let root = server_render("hydrate-1", &view(Rc::clone(&clicked)));
+    let button_before = sauron_core::document()
+        .get_element_by_id("hydrate-button")
+        .unwrap();
+
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new(view(clicked_clone), &root);
+    dom_updater.hydrate(&simple_program);
+
+    let button_after = sauron_core::document()
+        .get_element_by_id("hydrate-button")
+        .unwrap();
+    assert!(
+        button_before.is_same_node(Some(&button_after)),
+        "hydration should not recreate the elements"
+    );
+    assert_eq!(dom_updater.active_closure_len(), 1);
+
+    let html_button: &web_sys::HtmlElement = button_after.unchecked_ref();
+    html_button.click();
+    assert!(*clicked.borrow());
+}
+
+#[wasm_bindgen_test]
+fn hydrate_fixes_mismatched_text_and_missing_children() {
+    console_error_panic_hook::set_once();
+    let server: Node<()> = div(
+        vec![id("hydrate-2-root")],
+        vec![p(vec![], vec![text("from server")])],
+    );
+    let client: Node<()> = div(
+        vec![id("hydrate-2-root")],
+        vec![
+            p(vec![], vec![text("from client")]),
+            span(vec![], vec![text("added")]),
+        ],
+    );
+    let root = server_render("hydrate-2", &server);
+
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new(client, &root);
+    dom_updater.hydrate(&simple_program);
+
+    let root = sauron_core::document()
+        .get_element_by_id("hydrate-2-root")
+        .unwrap();
+    assert_eq!(
+        root.inner_html(),
+        "<p>from client</p><span>added</span>"
+    );
+}
@@ -0,0 +1,41 @@
+#![deny(warnings)]
+use sauron_core::{
+    html::{attributes::*, *},
+    initial_state::{initial_state_script, render_with_initial_state},
+    Node, Render,
+};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Model {
+    count: u32,
+    name: String,
+}
+
+#[test]
+fn initial_state_is_rendered_after_the_node() {
+    let model = Model {
+        count: 1,
+        name: "John".to_string(),
+    };
+    let view: Node<()> = div(vec![class("app")], vec![]);
+    let mut buffer = String::new();
+    render_with_initial_state(&view, &model, &mut buffer).unwrap();
+    let expected = r#"<div class="app"></div><script type="application/json" id="sauron-initial-state">{"count":1,"name":"John"}</script>"#;
+    assert_eq!(expected, buffer);
+}
+
+#[test]
+fn initial_state_can_not_close_the_script() {
+    let model = Model {
+        count: 1,
+        name: "</script><script>alert(1)</script>".to_string(),
+    };
+    let node: Node<()> = initial_state_script(&model).unwrap();
+    let mut buffer = String::new();
+    node.render(&mut buffer).unwrap();
+    assert!(!buffer.contains("</script><script>"));
+    let json = node.get_children().unwrap()[0].text().unwrap();
+    let value: serde_json::Value = serde_json::from_str(json).unwrap();
+    assert_eq!(value["name"], "</script><script>alert(1)</script>");
+}
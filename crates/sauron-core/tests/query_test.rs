@@ -0,0 +1,150 @@
+#![deny(warnings)]
+use sauron_core::{
+    html::{attributes::*, *},
+    test_harness::{attribute_value, text_content},
+    Node, QuerySelector, Selector, SelectorError,
+};
+
+fn view() -> Node<()> {
+    div(
+        vec![id("app"), class("container")],
+        vec![
+            ul(
+                vec![class("list")],
+                vec![
+                    li(
+                        vec![class("item"), class("first"), attr("data-id", 1)],
+                        vec![a(vec![href("/one")], vec![text("one")])],
+                    ),
+                    li(
+                        vec![class("item"), attr("data-id", 2)],
+                        vec![
+                            text("two"),
+                            ul(
+                                vec![class("nested")],
+                                vec![li(
+                                    vec![class("item"), attr("data-id", 3)],
+                                    vec![text("three")],
+                                )],
+                            ),
+                        ],
+                    ),
+                ],
+            ),
+            input(
+                vec![type_("checkbox"), checked(true), disabled(false)],
+                vec![],
+            ),
+        ],
+    )
+}
+
+fn texts(nodes: Vec<&Node<()>>) -> Vec<String> {
+    nodes.into_iter().map(text_content).collect()
+}
+
+#[test]
+fn simple_selectors() {
+    let view = view();
+    assert_eq!(3, view.query_selector_all("li").len());
+    assert_eq!(3, view.query_selector_all(".item").len());
+    assert_eq!(1, view.query_selector_all("#app").len());
+    assert_eq!(1, view.query_selector_all("li.item.first").len());
+    assert_eq!(
+        vec!["three"],
+        texts(view.query_selector_all("[data-id=\"3\"]"))
+    );
+    assert_eq!(
+        vec!["three"],
+        texts(view.query_selector_all("li[data-id=3]"))
+    );
+    assert_eq!(3, view.query_selector_all("[data-id]").len());
+    assert_eq!(8, view.query_selector_all("*").len());
+}
+
+#[test]
+fn boolean_attributes_are_present_when_enabled() {
+    let view = view();
+    assert_eq!(1, view.query_selector_all("input[checked]").len());
+    assert_eq!(0, view.query_selector_all("input[disabled]").len());
+}
+
+#[test]
+fn descendant_and_child_combinators() {
+    let view = view();
+    assert_eq!(
+        vec!["one", "twothree", "three"],
+        texts(view.query_selector_all("#app li"))
+    );
+    assert_eq!(
+        vec!["one", "twothree"],
+        texts(view.query_selector_all(".list > li"))
+    );
+    assert_eq!(
+        vec!["three"],
+        texts(view.query_selector_all("li > ul > .item"))
+    );
+    assert_eq!(
+        vec!["three"],
+        texts(view.query_selector_all("div li ul li"))
+    );
+    assert_eq!(1, view.query_selector_all("div>ul>li>a").len());
+    assert!(view.query_selector("div > li").is_none());
+}
+
+#[test]
+fn first_match_in_document_order() {
+    let view = view();
+    let item = view.query_selector(".item").expect("must have item");
+    assert_eq!(Some("1".to_string()), attribute_value(item, "data-id"));
+    assert!(view.query_selector("table").is_none());
+}
+
+#[test]
+fn query_selector_mut() {
+    let mut view = view();
+    view.query_selector_mut("a[href=\"/one\"]")
+        .expect("must have link")
+        .add_attributes_ref_mut(vec![class("active")]);
+    assert_eq!(1, view.query_selector_all("li > a.active").len());
+}
+
+#[test]
+fn query_selector_all_mut_visits_nested_matches() {
+    let mut view = view();
+    let mut visited = vec![];
+    view.query_selector_all_mut("li", |li| {
+        visited.push(attribute_value(li, "data-id").unwrap());
+        li.add_attributes_ref_mut(vec![class("seen")]);
+    });
+    assert_eq!(vec!["3", "2", "1"], visited);
+    assert_eq!(3, view.query_selector_all("li.seen").len());
+}
+
+#[test]
+fn invalid_selectors() {
+    assert_eq!(Err(SelectorError::Empty), Selector::parse("  "));
+    assert_eq!(
+        Err(SelectorError::DanglingCombinator),
+        Selector::parse("ul >")
+    );
+    assert_eq!(
+        Err(SelectorError::Unexpected('>', 0)),
+        Selector::parse("> li")
+    );
+    assert_eq!(
+        Err(SelectorError::UnclosedAttribute),
+        Selector::parse("[a=\"b")
+    );
+    assert_eq!(
+        Err(SelectorError::Unexpected('~', 2)),
+        Selector::parse("ul~li")
+    );
+    assert!("ul > li.item[data-id='1']".parse::<Selector>().is_ok());
+}
+
+#[test]
+#[should_panic(expected = "invalid selector")]
+fn querying_with_invalid_selector_panics() {
+    view().query_selector("li[");
+}
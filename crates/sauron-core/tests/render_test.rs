@@ -0,0 +1,321 @@
+#![deny(warnings)]
+use sauron_core::{
+    html::{attributes::*, *},
+    Callback, HtmlWriter, Node, Render, RenderChunks, RenderOptions,
+};
+use std::io;
+
+fn view() -> Node<()> {
+    div(
+        vec![class("app")],
+        vec![p(
+            vec![],
+            vec![text("Hello "), b(vec![], vec![text("world")])],
+        )],
+    )
+}
+
+#[test]
+fn pretty_is_the_default() {
+    let mut buffer = String::new();
+    view().render(&mut buffer).unwrap();
+    let expected = "<div class=\"app\">\n    <p>\n        Hello \n        <b>world</b>\n    </p>\n</div>";
+    assert_eq!(expected, buffer);
+}
+
+#[test]
+fn pretty_with_indent() {
+    let mut buffer = String::new();
+    view()
+        .render_with_options(
+            &mut buffer,
+            &RenderOptions::pretty().with_indent(2),
+        )
+        .unwrap();
+    let expected = "<div class=\"app\">\n  <p>\n    Hello \n    <b>world</b>\n  </p>\n</div>";
+    assert_eq!(expected, buffer);
+}
+
+#[test]
+fn compact_adds_no_whitespace() {
+    let mut buffer = String::new();
+    view().render_compact(&mut buffer).unwrap();
+    let expected = "<div class=\"app\"><p>Hello <b>world</b></p></div>";
+    assert_eq!(expected, buffer);
+}
+
+#[test]
+fn document_starts_with_doctype() {
+    let page: Node<()> = html(vec![], vec![body(vec![], vec![])]);
+    let mut buffer = String::new();
+    page.render_with_options(
+        &mut buffer,
+        &RenderOptions::compact().with_doctype(),
+    )
+    .unwrap();
+    assert_eq!("<!DOCTYPE html><html><body></body></html>", buffer);
+
+    let mut buffer = String::new();
+    page.render_with_options(
+        &mut buffer,
+        &RenderOptions::default().with_doctype(),
+    )
+    .unwrap();
+    assert_eq!(
+        "<!DOCTYPE html>\n<html>\n    <body></body>\n</html>",
+        buffer
+    );
+}
+
+#[test]
+fn text_is_escaped() {
+    let node: Node<()> =
+        div(vec![], vec![text("<script>alert(\"x\")</script> & more")]);
+    let mut buffer = String::new();
+    node.render(&mut buffer).unwrap();
+    assert_eq!(
+        "<div>&lt;script&gt;alert(\"x\")&lt;/script&gt; &amp; more</div>",
+        buffer
+    );
+}
+
+#[test]
+fn attribute_values_are_escaped() {
+    let node: Node<()> = a(
+        vec![title("\"><img src=x onerror=alert(1)>"), href("/?a=1&b=2")],
+        vec![],
+    );
+    let mut buffer = String::new();
+    node.render(&mut buffer).unwrap();
+    assert_eq!(
+        "<a title=\"&quot;&gt;&lt;img src=x onerror=alert(1)&gt;\" href=\"/?a=1&amp;b=2\"></a>",
+        buffer
+    );
+}
+
+#[test]
+fn script_and_style_are_raw_text() {
+    let node: Node<()> = div(
+        vec![],
+        vec![
+            script(vec![], vec![text("if (a < b && c > d) {}")]),
+            tags::style(vec![], vec![text("div > p { content: \"&\"; }")]),
+        ],
+    );
+    let mut buffer = String::new();
+    node.render_compact(&mut buffer).unwrap();
+    assert_eq!(
+        "<div><script>if (a < b && c > d) {}</script><style>div > p { content: \"&\"; }</style></div>",
+        buffer
+    );
+}
+
+#[test]
+fn void_elements_are_not_closed() {
+    let node: Node<()> = div(
+        vec![],
+        vec![br(vec![], vec![]), img(vec![src("a.png")], vec![])],
+    );
+    let mut buffer = String::new();
+    node.render_compact(&mut buffer).unwrap();
+    assert_eq!("<div><br><img src=\"a.png\"></div>", buffer);
+
+    let mut buffer = String::new();
+    node.render_with_options(
+        &mut buffer,
+        &RenderOptions::compact().with_xhtml(),
+    )
+    .unwrap();
+    assert_eq!("<div><br/><img src=\"a.png\"/></div>", buffer);
+}
+
+#[test]
+fn empty_svg_elements_are_self_closing() {
+    use sauron_core::svg::{attributes::cx, *};
+    let node: Node<()> = svg(vec![], vec![circle(vec![cx(50)], vec![])]);
+    let mut buffer = String::new();
+    node.render_compact(&mut buffer).unwrap();
+    assert_eq!("<svg><circle cx=\"50\"/></svg>", buffer);
+}
+
+#[test]
+fn boolean_attributes() {
+    let node: Node<()> = input(
+        vec![
+            type_("checkbox"),
+            checked(true),
+            disabled(false),
+            attr("readonly", false),
+            attr("required", true),
+        ],
+        vec![],
+    );
+    let mut buffer = String::new();
+    node.render_compact(&mut buffer).unwrap();
+    assert_eq!("<input type=\"checkbox\" checked required>", buffer);
+
+    let mut buffer = String::new();
+    node.render_with_options(
+        &mut buffer,
+        &RenderOptions::compact().with_xhtml(),
+    )
+    .unwrap();
+    assert_eq!(
+        "<input type=\"checkbox\" checked=\"checked\" required=\"required\"/>",
+        buffer
+    );
+}
+
+#[test]
+fn event_listeners_are_not_rendered() {
+    let node: Node<()> = button(
+        vec![
+            class("btn"),
+            sauron_core::mt_dom::on("click", Callback::from(|_| ())),
+        ],
+        vec![],
+    );
+    let mut buffer = String::new();
+    node.render_compact(&mut buffer).unwrap();
+    assert_eq!("<button class=\"btn\"></button>", buffer);
+}
+
+#[test]
+fn attributes_with_the_same_name_are_merged() {
+    let node: Node<()> = div(
+        vec![
+            class("a"),
+            id("first"),
+            style("color", "red"),
+            class("b"),
+            id("second"),
+            style("display", "flex"),
+            classes_flag([("c", true), ("d", false)]),
+        ],
+        vec![],
+    );
+    let mut buffer = String::new();
+    node.render_compact(&mut buffer).unwrap();
+    assert_eq!(
+        "<div class=\"a b c\" id=\"second\" style=\"color:red;display:flex;\"></div>",
+        buffer
+    );
+}
+
+#[test]
+fn plain_style_values_are_separated() {
+    let node: Node<()> = div(
+        vec![attr("style", "color:red"), style("display", "flex")],
+        vec![],
+    );
+    let mut buffer = String::new();
+    node.render_compact(&mut buffer).unwrap();
+    assert_eq!("<div style=\"color:red;display:flex;\"></div>", buffer);
+}
+
+fn table_view() -> Node<()> {
+    html(
+        vec![],
+        vec![body(
+            vec![],
+            vec![
+                tags::style(vec![], vec![text("td > b { color: red; }")]),
+                table(
+                    vec![class("grid")],
+                    (0..100)
+                        .map(|row| {
+                            tr(
+                                vec![],
+                                vec![
+                                    td(vec![], vec![text(row)]),
+                                    td(
+                                        vec![],
+                                        vec![
+                                            text("<row>"),
+                                            b(vec![], vec![text(row * 2)]),
+                                            br(vec![], vec![]),
+                                        ],
+                                    ),
+                                ],
+                            )
+                        })
+                        .collect(),
+                ),
+            ],
+        )],
+    )
+}
+
+#[test]
+fn chunks_are_the_same_as_render() {
+    let node = table_view();
+    for options in [
+        RenderOptions::default().with_doctype(),
+        RenderOptions::compact(),
+        RenderOptions::pretty().with_indent(2).with_xhtml(),
+    ] {
+        let mut expected = String::new();
+        node.render_with_options(&mut expected, &options).unwrap();
+
+        let chunks: Vec<Vec<u8>> =
+            RenderChunks::new(&node, options, 256).collect();
+        assert!(chunks.len() > 1);
+        assert!(chunks[..chunks.len() - 1].iter().all(|c| c.len() >= 256));
+        assert_eq!(expected.into_bytes(), chunks.concat());
+    }
+}
+
+/// records each write, to check the html is written in chunks
+#[derive(Default)]
+struct Recorder {
+    writes: Vec<usize>,
+    flushes: usize,
+    data: Vec<u8>,
+}
+
+impl io::Write for Recorder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writes.push(buf.len());
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushes += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn html_writer_writes_in_chunks() {
+    let node = table_view();
+    let mut expected = String::new();
+    node.render(&mut expected).unwrap();
+
+    let mut writer = HtmlWriter::with_chunk_size(Recorder::default(), 512);
+    node.render(&mut writer).unwrap();
+    let recorder = writer.finish().unwrap();
+    assert!(recorder.writes.len() > 1);
+    assert_eq!(recorder.writes.len(), recorder.flushes);
+    assert_eq!(expected.into_bytes(), recorder.data);
+}
+
+struct Broken;
+
+impl io::Write for Broken {
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn html_writer_returns_the_io_error() {
+    let mut writer = HtmlWriter::with_chunk_size(Broken, 16);
+    assert!(table_view().render(&mut writer).is_err());
+    let error = writer.finish().err().unwrap();
+    assert_eq!(io::ErrorKind::BrokenPipe, error.kind());
+}
@@ -0,0 +1,81 @@
+#![deny(warnings)]
+use sauron_core::router::{Route, Url};
+
+#[derive(Debug, PartialEq, Clone)]
+enum Page {
+    Home,
+    Article(u32),
+    Search(String),
+}
+
+impl Route for Page {
+    fn from_url(url: &Url) -> Option<Self> {
+        match url.segments().as_slice() {
+            [] => Some(Page::Home),
+            ["article", id] => id.parse().ok().map(Page::Article),
+            ["search"] => Some(Page::Search(url.query("q")?.to_string())),
+            _ => None,
+        }
+    }
+
+    fn to_url(&self) -> String {
+        match self {
+            Page::Home => "/".to_string(),
+            Page::Article(id) => format!("/article/{}", id),
+            Page::Search(q) => {
+                Url::new("/search").with_query("q", q).to_string()
+            }
+        }
+    }
+}
+
+#[test]
+fn parse_path_query_and_hash() {
+    let url = Url::parse("/search?q=a+b%26c&page=2#results");
+    assert_eq!(url.path, "/search");
+    assert_eq!(url.query("q"), Some("a b&c"));
+    assert_eq!(url.query("page"), Some("2"));
+    assert_eq!(url.query("none"), None);
+    assert_eq!(url.hash, Some("results".to_string()));
+}
+
+#[test]
+fn scheme_and_host_are_ignored() {
+    let url = Url::parse("https://example.com/article/3?x");
+    assert_eq!(url.path, "/article/3");
+    assert_eq!(url.query("x"), Some(""));
+    assert_eq!(Url::parse("https://example.com").path, "/");
+}
+
+#[test]
+fn invalid_escapes_are_kept() {
+    let url = Url::parse("/?q=100%&r=%zz&s=%e2%9c%93");
+    assert_eq!(url.query("q"), Some("100%"));
+    assert_eq!(url.query("r"), Some("%zz"));
+    assert_eq!(url.query("s"), Some("✓"));
+}
+
+#[test]
+fn route_is_parsed() {
+    assert_eq!(Page::from_url(&Url::parse("/")), Some(Page::Home));
+    assert_eq!(
+        Page::from_url(&Url::parse("/article/42/")),
+        Some(Page::Article(42))
+    );
+    assert_eq!(Page::from_url(&Url::parse("/article/abc")), None);
+    assert_eq!(Page::from_url(&Url::parse("/search")), None);
+}
+
+#[test]
+fn route_round_trip() {
+    let page = Page::Search("rust & wasm".to_string());
+    assert_eq!(page.to_url(), "/search?q=rust%20%26%20wasm");
+    assert_eq!(Page::from_url(&Url::parse(&page.to_url())), Some(page));
+}
+
+#[test]
+fn hash_routes() {
+    let url = Url::parse("/index.html#/article/7");
+    assert_eq!(Page::from_url(&url.hash_url()), Some(Page::Article(7)));
+    assert_eq!(url.to_string(), "/index.html#/article/7");
+}
@@ -0,0 +1,72 @@
+#![deny(warnings)]
+use sauron_core::{
+    html::{attributes::*, *},
+    Cmd, Component, Dispatch, Node, Render, ServerProgram,
+};
+
+enum Msg {
+    Increment,
+    Greet(String),
+}
+
+#[derive(Default)]
+struct App {
+    count: u32,
+    name: Option<String>,
+}
+
+impl Component<Msg> for App {
+    fn init(&self) -> Cmd<Self, Msg> {
+        Cmd::new(|program| program.dispatch(Msg::Increment))
+    }
+
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Increment => {
+                self.count += 1;
+                Cmd::none()
+            }
+            Msg::Greet(name) => {
+                self.name = Some(name);
+                Cmd::new(|program| program.dispatch(Msg::Increment))
+            }
+        }
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            vec![class("app")],
+            vec![
+                p(vec![], vec![text(format!("count: {}", self.count))]),
+                if let Some(name) = &self.name {
+                    p(vec![], vec![text(format!("Hello {}!", name))])
+                } else {
+                    span(vec![], vec![])
+                },
+            ],
+        )
+    }
+}
+
+#[test]
+fn init_cmd_is_emitted() {
+    let program = ServerProgram::new(App::default());
+    assert_eq!(program.app.borrow().count, 1);
+}
+
+#[test]
+fn dispatched_msgs_updates_the_rendered_view() {
+    let program = ServerProgram::with_msgs(
+        App::default(),
+        vec![Msg::Increment, Msg::Greet("John".to_string())],
+    );
+    assert_eq!(program.app.borrow().count, 3);
+
+    let mut buffer = String::new();
+    program.render(&mut buffer).expect("must render");
+    let expected = r#"<div class="app">
+    <p>count: 3</p>
+    <p>Hello John!</p>
+</div>"#;
+    assert_eq!(expected, buffer);
+}
@@ -0,0 +1,98 @@
+#![deny(warnings)]
+use sauron_core::{
+    assert_snapshot,
+    html::{attributes::*, *},
+    snapshot::{check_snapshot, diff, normalize, SnapshotError},
+    Node,
+};
+use std::{env, fs};
+
+fn view(items: &[&str]) -> Node<()> {
+    div(
+        vec![id("app"), class("main"), class("container")],
+        vec![
+            h1(vec![], vec![text("Todos")]),
+            ul(
+                vec![class("list")],
+                items
+                    .iter()
+                    .map(|item| {
+                        li(
+                            vec![classes_flag([
+                                ("done", false),
+                                ("item", true),
+                            ])],
+                            vec![text(item)],
+                        )
+                    })
+                    .collect(),
+            ),
+        ],
+    )
+}
+
+#[test]
+fn attributes_and_classes_are_sorted() {
+    let node: Node<()> = input(
+        vec![
+            type_("text"),
+            class("b a"),
+            value("x"),
+            class("c a"),
+            disabled(true),
+        ],
+        vec![],
+    );
+    assert_eq!(
+        "<input class=\"a b c\" disabled type=\"text\" value=\"x\">",
+        normalize(&node)
+    );
+}
+
+#[test]
+fn matches_the_stored_snapshot() {
+    assert_snapshot!("todos", &view(&["milk", "eggs"]));
+}
+
+#[test]
+fn mismatch_shows_where_the_changes_are() {
+    let path = env::current_dir()
+        .unwrap()
+        .join("tests")
+        .join("snapshots")
+        .join("todos.snap");
+    match check_snapshot(&path, &view(&["milk", "bread", "eggs"])) {
+        Err(SnapshotError::Mismatch { diff, .. }) => assert_eq!(
+            "@@ div > ul @@\n   <ul class=\"list\">\n     <li class=\"item\">milk</li>\n+    <li class=\"item\">bread</li>\n     <li class=\"item\">eggs</li>\n   </ul>\n",
+            diff
+        ),
+        other => panic!("expecting a mismatch, got: {:?}", other),
+    }
+}
+
+#[test]
+fn missing_snapshot() {
+    let path = env::temp_dir().join("sauron-missing.snap");
+    let _ = fs::remove_file(&path);
+    let result = check_snapshot(&path, &view(&[]));
+    assert!(matches!(result, Err(SnapshotError::Missing(_))));
+}
+
+#[test]
+fn diff_of_changed_attribute() {
+    let old = normalize(&view(&["milk"]));
+    let new: Node<()> = div(
+        vec![id("app"), class("main container")],
+        vec![
+            h1(vec![class("title")], vec![text("Todos")]),
+            ul(
+                vec![class("list")],
+                vec![li(vec![class("item")], vec![text("milk")])],
+            ),
+        ],
+    );
+    assert_eq!(
+        "@@ div @@\n <div class=\"container main\" id=\"app\">\n-  <h1>Todos</h1>\n+  <h1 class=\"title\">Todos</h1>\n   <ul class=\"list\">\n     <li class=\"item\">milk</li>\n",
+        diff(&old, &normalize(&new))
+    );
+}
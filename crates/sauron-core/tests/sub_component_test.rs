@@ -0,0 +1,106 @@
+#![deny(warnings)]
+use sauron_core::{
+    html::{attributes::*, *},
+    Cmd, Component, DomUpdater, Node, SubComponent,
+};
+use test_fixtures::simple_program;
+use wasm_bindgen_test::*;
+
+mod test_fixtures;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Counter {
+    count: u32,
+}
+
+impl Component<()> for Counter {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        self.count += 1;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        span(vec![class("counter")], vec![text(self.count)])
+    }
+}
+
+#[wasm_bindgen_test]
+fn sub_component_is_mounted_in_the_placeholder() {
+    console_error_panic_hook::set_once();
+    let counter = SubComponent::new(Counter { count: 5 });
+
+    let old: Node<()> = div(
+        vec![id("sub-component-parent")],
+        vec![p(vec![], vec![text("before")]), counter.view()],
+    );
+    let new: Node<()> = div(
+        vec![id("sub-component-parent")],
+        vec![
+            p(vec![], vec![text("after")]),
+            counter.view(),
+            p(vec![], vec![text("appended")]),
+        ],
+    );
+
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new_append_to_mount(
+        &simple_program,
+        old,
+        &sauron_core::body(),
+    );
+    let parent = sauron_core::document()
+        .get_element_by_id("sub-component-parent")
+        .unwrap();
+    let placeholder = parent.children().item(1).unwrap();
+    assert_eq!(
+        placeholder.inner_html(),
+        r#"<span class="counter">5</span>"#
+    );
+
+    // patching the parent doesn't touch the content of the sub component
+    dom_updater.update_dom(&simple_program, new);
+    assert_eq!(
+        parent.inner_html(),
+        format!(r#"<p>after</p>{}<p>appended</p>"#, placeholder.outer_html())
+    );
+    assert_eq!(
+        placeholder.inner_html(),
+        r#"<span class="counter">5</span>"#
+    );
+    assert_eq!(counter.with_app(|app| app.count), 5);
+}
+
+struct Badge;
+
+impl Component<()> for Badge {
+    fn style(&self) -> Vec<String> {
+        vec![".sub-component-badge { color: teal; }".to_string()]
+    }
+
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        span(vec![class("sub-component-badge")], vec![])
+    }
+}
+
+#[wasm_bindgen_test]
+fn style_is_injected_once_per_component_type() {
+    let _badges = [SubComponent::new(Badge), SubComponent::new(Badge)];
+
+    let styles = sauron_core::document()
+        .head()
+        .expect("must have a head")
+        .get_elements_by_tag_name("style");
+    let injected = (0..styles.length())
+        .filter_map(|i| styles.item(i))
+        .filter(|style| {
+            style.text_content().as_deref()
+                == Some(".sub-component-badge { color: teal; }")
+        })
+        .count();
+    assert_eq!(injected, 1);
+}
@@ -0,0 +1,83 @@
+#![deny(warnings)]
+use sauron_core::{html::*, Cmd, Component, Node, Sub};
+
+enum Msg {
+    Tick,
+    Toggle,
+}
+
+struct Clock {
+    ticks: u32,
+    running: bool,
+}
+
+impl Component<Msg> for Clock {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Tick => self.ticks += 1,
+            Msg::Toggle => self.running = !self.running,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(vec![], vec![text(self.ticks)])
+    }
+
+    fn subscriptions(&self) -> Sub<Msg> {
+        if self.running {
+            Sub::batch(vec![
+                Sub::every(1000, || Msg::Tick),
+                Sub::on_animation_frame(|_| Msg::Tick),
+            ])
+        } else {
+            Sub::none()
+        }
+    }
+}
+
+struct Counter;
+
+impl Component<()> for Counter {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        div(vec![], vec![])
+    }
+}
+
+#[test]
+fn subscriptions_follow_the_state() {
+    let mut clock = Clock {
+        ticks: 0,
+        running: true,
+    };
+    assert!(!clock.subscriptions().is_none());
+    clock.update(Msg::Tick);
+    clock.update(Msg::Toggle);
+    assert_eq!(clock.ticks, 1);
+    assert!(clock.subscriptions().is_none());
+}
+
+#[test]
+fn default_subscriptions_is_none() {
+    assert!(Counter.subscriptions().is_none());
+}
+
+#[test]
+fn mapped_sub_is_kept() {
+    let sub: Sub<Option<()>> = Sub::every(100, || ()).map_msg(Some);
+    assert!(!sub.is_none());
+    assert!(Sub::<()>::batch(vec![Sub::none(), Sub::none()]).is_none());
+}
+
+#[test]
+fn filter_map_sub_is_kept() {
+    let sub: Sub<()> = Sub::batch(vec![
+        Sub::on_window_filter_map("storage", |_| None),
+        Sub::on_document_filter_map("keydown", |_| Some(())),
+    ]);
+    assert!(!sub.is_none());
+}
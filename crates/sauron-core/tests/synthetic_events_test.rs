@@ -0,0 +1,425 @@
+#![deny(warnings)]
+#![cfg(not(feature = "with-dom"))]
+use sauron_core::{
+    html::{attributes::*, events::*, *},
+    test_harness::{emit_event, trigger},
+    File, Node, QuerySelector,
+};
+
+#[derive(Debug, PartialEq)]
+enum Msg {
+    Click(i32, i32),
+    Secondary,
+    Key(String, bool),
+    Input(String),
+    Scrolled(i32, i32),
+    Submit,
+}
+
+fn view() -> Node<Msg> {
+    form(
+        vec![
+            class("editor"),
+            on_submit(|event| {
+                event.prevent_default();
+                Msg::Submit
+            }),
+        ],
+        vec![
+            input(
+                vec![
+                    id("title"),
+                    class("field"),
+                    on_input(|event| Msg::Input(event.value)),
+                    on_keydown(|event| Msg::Key(event.key(), event.ctrl_key())),
+                ],
+                vec![],
+            ),
+            button(
+                vec![
+                    class("btn"),
+                    class("save"),
+                    on_click(|event| {
+                        if event.button() == 2 {
+                            Msg::Secondary
+                        } else {
+                            Msg::Click(event.client_x(), event.client_y())
+                        }
+                    }),
+                ],
+                vec![text("save")],
+            ),
+            div(
+                vec![
+                    class("list"),
+                    on_scroll(|(top, left)| Msg::Scrolled(top, left)),
+                ],
+                vec![],
+            ),
+        ],
+    )
+}
+
+#[test]
+fn mouse_event() {
+    let view = view();
+    assert_eq!(
+        Msg::Click(10, 20),
+        trigger(&view, "button.save", MouseEvent::new(10, 20))
+    );
+    assert_eq!(
+        Msg::Secondary,
+        trigger(&view, ".btn", MouseEvent::new(0, 0).with_button(2))
+    );
+}
+
+#[test]
+fn mouse_listeners_other_than_click() {
+    let view: Node<Msg> = div(
+        vec![
+            id("canvas"),
+            on_mousedown(|event| {
+                Msg::Click(event.client_x(), event.client_y())
+            }),
+        ],
+        vec![],
+    );
+    assert_eq!(
+        Msg::Click(3, 4),
+        trigger(&view, "#canvas", MouseEvent::new(3, 4))
+    );
+}
+
+#[test]
+fn wheel_event() {
+    let view: Node<(f64, f64, bool)> = div(
+        vec![
+            class("zoomable"),
+            on_wheel(|event| {
+                (event.delta_x(), event.delta_y(), event.ctrl_key())
+            }),
+        ],
+        vec![],
+    );
+    assert_eq!(
+        (0.0, -120.0, true),
+        trigger(&view, ".zoomable", WheelEvent::new(0.0, -120.0).with_ctrl())
+    );
+}
+
+#[test]
+fn keyboard_event() {
+    let view = view();
+    assert_eq!(
+        Msg::Key("Enter".to_string(), false),
+        trigger(&view, "#title", KeyboardEvent::new("Enter"))
+    );
+    assert_eq!(
+        Msg::Key("s".to_string(), true),
+        trigger(&view, "input#title", KeyboardEvent::new("s").with_ctrl())
+    );
+}
+
+#[test]
+fn input_event() {
+    let view = view();
+    assert_eq!(
+        Msg::Input("hello".to_string()),
+        trigger(&view, ".field", InputEvent::new("hello".to_string()))
+    );
+}
+
+#[test]
+fn scroll_and_plain_events() {
+    let view = view();
+    assert_eq!(
+        Msg::Scrolled(100, 5),
+        trigger(&view, ".list", SyntheticEvent::Scroll(100, 5))
+    );
+    assert_eq!(Msg::Submit, trigger(&view, "form", SyntheticEvent::Plain));
+}
+
+#[test]
+fn emit_event_with_other_event_names() {
+    let view = view();
+    let title = view.query_selector_all("#title");
+    assert_eq!(
+        vec![Msg::Key("a".to_string(), false)],
+        emit_event(title[0], "keydown", KeyboardEvent::new("a").into())
+    );
+    assert!(
+        emit_event(title[0], "keyup", KeyboardEvent::new("a").into())
+            .is_empty()
+    );
+}
+
+#[test]
+fn filtered_events_emit_no_msg() {
+    let view: Node<Msg> = button(
+        vec![
+            class("btn"),
+            on_filter_map("click", |event| match event {
+                SyntheticEvent::Mouse(mouse) if mouse.button() == 0 => {
+                    Some(Msg::Click(mouse.client_x(), mouse.client_y()))
+                }
+                _ => None,
+            }),
+        ],
+        vec![],
+    );
+    let button = view.query_selector_all(".btn");
+    assert_eq!(
+        vec![Msg::Click(1, 2)],
+        emit_event(button[0], "click", MouseEvent::new(1, 2).into())
+    );
+    assert!(emit_event(
+        button[0],
+        "click",
+        MouseEvent::new(1, 2).with_button(1).into()
+    )
+    .is_empty());
+}
+
+#[test]
+#[should_panic(expected = "has no listener")]
+fn trigger_without_listener_panics() {
+    trigger(&view(), ".list", MouseEvent::new(0, 0));
+}
+
+#[test]
+#[should_panic(expected = "unable to cast")]
+fn mismatched_event_panics() {
+    let view = view();
+    let button = view.query_selector_all("button");
+    emit_event(button[0], "click", KeyboardEvent::new("a").into());
+}
+
+#[derive(Debug, PartialEq)]
+enum Gesture {
+    Pointer(i32, String, bool),
+    Touches(u32, Option<i32>),
+    Dropped(String),
+    Focused(Option<String>),
+    Pasted(String),
+    Composing(Option<String>),
+}
+
+fn gesture_view() -> Node<Gesture> {
+    div(
+        vec![],
+        vec![
+            div(
+                vec![
+                    class("canvas"),
+                    on_pointerdown(|event| {
+                        Gesture::Pointer(
+                            event.pointer_id(),
+                            event.pointer_type(),
+                            event.is_primary(),
+                        )
+                    }),
+                    on_touchstart(|event| {
+                        Gesture::Touches(
+                            event.touches().length(),
+                            event
+                                .changed_touches()
+                                .get(0)
+                                .map(|t| t.identifier()),
+                        )
+                    }),
+                    on_drop(|event| {
+                        let data = event
+                            .data_transfer()
+                            .and_then(|data| data.get_data("text/plain").ok())
+                            .unwrap_or_default();
+                        Gesture::Dropped(data)
+                    }),
+                ],
+                vec![],
+            ),
+            input(
+                vec![
+                    id("name"),
+                    on_focus(|event| Gesture::Focused(event.related_target())),
+                    on_paste(|event| {
+                        let data = event
+                            .clipboard_data()
+                            .and_then(|data| data.get_data("text/plain").ok())
+                            .unwrap_or_default();
+                        Gesture::Pasted(data)
+                    }),
+                    on_compositionupdate(|event| {
+                        Gesture::Composing(event.data())
+                    }),
+                ],
+                vec![],
+            ),
+        ],
+    )
+}
+
+#[test]
+fn pointer_and_touch_events() {
+    let view = gesture_view();
+    assert_eq!(
+        Gesture::Pointer(3, "pen".to_string(), false),
+        trigger(
+            &view,
+            ".canvas",
+            PointerEvent::new(1, 2)
+                .with_pointer_id(3)
+                .with_pointer_type("pen")
+                .with_secondary()
+        )
+    );
+    assert_eq!(
+        Gesture::Touches(2, Some(7)),
+        trigger(
+            &view,
+            ".canvas",
+            TouchEvent::new(vec![Touch::new(6, 0, 0), Touch::new(7, 5, 5)])
+                .with_changed_touches(vec![Touch::new(7, 5, 5)])
+        )
+    );
+}
+
+#[test]
+fn drag_focus_clipboard_and_composition_events() {
+    let view = gesture_view();
+    let data = DataTransfer::new().with_data("text/plain", "dropped");
+    assert_eq!(
+        Gesture::Dropped("dropped".to_string()),
+        trigger(&view, ".canvas", DragEvent::new(0, 0, data))
+    );
+    assert_eq!(
+        Gesture::Focused(Some("search".to_string())),
+        trigger(
+            &view,
+            "#name",
+            FocusEvent::new().with_related_target("search")
+        )
+    );
+    let data = DataTransfer::new().with_data("text/plain", "pasted");
+    assert_eq!(
+        Gesture::Pasted("pasted".to_string()),
+        trigger(&view, "#name", ClipboardEvent::new(data))
+    );
+    assert_eq!(
+        Gesture::Composing(Some("にほ".to_string())),
+        trigger(&view, "#name", CompositionEvent::new("にほ"))
+    );
+}
+
+#[test]
+fn data_transfer_is_shared_with_the_listener() {
+    let view: Node<()> = div(
+        vec![
+            id("source"),
+            on_dragstart(|event| {
+                let data = event.data_transfer().expect("must have data");
+                data.set_data("text/plain", "item-1").unwrap();
+                data.set_drop_effect("move");
+            }),
+        ],
+        vec![],
+    );
+    let data = DataTransfer::new();
+    trigger(&view, "#source", DragEvent::new(0, 0, data.clone()));
+    assert_eq!(data.get_data("text/plain").unwrap(), "item-1");
+    assert_eq!(data.types(), vec!["text/plain".to_string()]);
+    assert_eq!(data.drop_effect(), "move");
+}
+
+#[derive(Debug, PartialEq)]
+enum Field {
+    Agreed(bool),
+    Colors(Vec<String>),
+    Uploaded(Vec<(String, u64)>),
+}
+
+#[test]
+fn form_control_values() {
+    let view: Node<Field> = form(
+        vec![],
+        vec![
+            input(
+                vec![
+                    r#type("checkbox"),
+                    id("agree"),
+                    on_change(|event| Field::Agreed(event.checked)),
+                ],
+                vec![],
+            ),
+            select(
+                vec![
+                    id("colors"),
+                    on_change(|event| Field::Colors(event.selected)),
+                ],
+                vec![],
+            ),
+            input(
+                vec![
+                    r#type("file"),
+                    id("upload"),
+                    on_change(|event| {
+                        Field::Uploaded(
+                            event
+                                .files
+                                .iter()
+                                .map(|file| {
+                                    (file.name().to_string(), file.size())
+                                })
+                                .collect(),
+                        )
+                    }),
+                ],
+                vec![],
+            ),
+        ],
+    );
+    assert_eq!(
+        Field::Agreed(true),
+        trigger(&view, "#agree", InputEvent::default().with_checked(true))
+    );
+    let selected = vec!["red".to_string(), "blue".to_string()];
+    assert_eq!(
+        Field::Colors(selected.clone()),
+        trigger(
+            &view,
+            "#colors",
+            InputEvent::default().with_selected(selected)
+        )
+    );
+    let file = File::new("notes.txt", "text/plain", b"hello".to_vec());
+    assert_eq!(file.content(), b"hello");
+    assert_eq!(
+        Field::Uploaded(vec![("notes.txt".to_string(), 5)]),
+        trigger(
+            &view,
+            "#upload",
+            InputEvent::default().with_files(vec![file])
+        )
+    );
+}
+
+#[test]
+fn dropped_files() {
+    let view: Node<Vec<String>> = div(
+        vec![
+            class("dropzone"),
+            on_drop_files(|files| {
+                files.iter().map(|file| file.name().to_string()).collect()
+            }),
+        ],
+        vec![],
+    );
+    let files = vec![
+        File::new("a.csv", "text/csv", b"id,name".to_vec()),
+        File::new("b.csv", "text/csv", vec![]),
+    ];
+    let data = DataTransfer::new().with_files(files);
+    assert_eq!(
+        vec!["a.csv".to_string(), "b.csv".to_string()],
+        trigger(&view, ".dropzone", DragEvent::new(0, 0, data))
+    );
+}
@@ -0,0 +1,133 @@
+#![deny(warnings)]
+use sauron_core::{
+    html::{attributes::*, events::*, *},
+    test_harness::{find_all_by_class, text_content, TestHarness},
+    Cmd, Component, Node,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    Add(String),
+    Remove(usize),
+    Load,
+    Loaded(Vec<String>),
+}
+
+#[derive(Default)]
+struct Todos {
+    items: Vec<String>,
+}
+
+impl Component<Msg> for Todos {
+    fn init(&self) -> Cmd<Self, Msg> {
+        Cmd::new(|program| program.dispatch(Msg::Load))
+    }
+
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Add(item) => self.items.push(item),
+            Msg::Remove(index) => {
+                self.items.remove(index);
+            }
+            Msg::Load => {
+                return Cmd::new(|program| {
+                    program.dispatch(Msg::Loaded(vec![
+                        "milk".to_string(),
+                        "eggs".to_string(),
+                    ]))
+                })
+            }
+            Msg::Loaded(items) => self.items = items,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            vec![],
+            vec![
+                h1(vec![id("count")], vec![text(self.items.len())]),
+                ul(
+                    vec![class("items")],
+                    self.items
+                        .iter()
+                        .enumerate()
+                        .map(|(index, item)| {
+                            li(
+                                vec![
+                                    class("item"),
+                                    classes_flag([("first", index == 0)]),
+                                ],
+                                vec![
+                                    span(vec![], vec![text(item)]),
+                                    button(
+                                        vec![
+                                            id(format!("remove-{}", index)),
+                                            on_click(move |_| {
+                                                Msg::Remove(index)
+                                            }),
+                                        ],
+                                        vec![text("x")],
+                                    ),
+                                ],
+                            )
+                        })
+                        .collect(),
+                ),
+            ],
+        )
+    }
+}
+
+#[test]
+fn init_cmd_is_captured_until_run() {
+    let harness = TestHarness::new(Todos::default());
+    assert_eq!(1, harness.cmd_count());
+    assert_eq!(Some("0".to_string()), harness.text_by_id("count"));
+
+    // the init dispatches Load, which returns another Cmd
+    harness.run_cmds();
+    assert_eq!(1, harness.cmd_count());
+    assert!(harness.app().items.is_empty());
+
+    harness.run_cmds();
+    assert_eq!(0, harness.cmd_count());
+    assert_eq!(vec!["milk", "eggs"], harness.app().items);
+    assert_eq!(Some("2".to_string()), harness.text_by_id("count"));
+}
+
+#[test]
+fn view_is_updated_after_each_msg() {
+    let harness = TestHarness::new(Todos::default());
+    harness.take_cmds();
+    harness.send_all(vec![Msg::Add("bread".into()), Msg::Add("jam".into())]);
+    assert_eq!(0, harness.cmd_count());
+    assert_eq!(vec!["breadx", "jamx"], harness.texts_by_class("item"));
+    assert_eq!(vec!["breadx"], harness.texts_by_class("first"));
+
+    let view = harness.view();
+    let items = find_all_by_class(&view, "items");
+    assert_eq!(1, items.len());
+    assert_eq!("breadxjamx", text_content(items[0]));
+}
+
+// the synthetic events are only used without the browser
+#[cfg(not(feature = "with-dom"))]
+#[test]
+fn trigger_calls_the_listener() {
+    let harness = TestHarness::new(Todos::default());
+    harness.send_all(vec![Msg::Add("bread".into()), Msg::Add("jam".into())]);
+    assert!(harness.has_id("remove-1"));
+
+    harness.trigger("#remove-0", "click", MouseEvent::new(0, 0));
+    assert_eq!(vec!["jamx"], harness.texts_by_class("item"));
+    assert!(!harness.has_id("remove-1"));
+}
+
+#[cfg(not(feature = "with-dom"))]
+#[test]
+#[should_panic(expected = "no element matching")]
+fn trigger_on_missing_element_panics() {
+    let harness = TestHarness::new(Todos::default());
+    harness.trigger("#remove-0", "click", MouseEvent::new(0, 0));
+}
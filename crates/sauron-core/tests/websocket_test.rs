@@ -0,0 +1,35 @@
+#![deny(warnings)]
+#![cfg(feature = "with-dom")]
+use sauron_core::Reconnect;
+
+#[test]
+fn reconnect_delay_is_doubled_up_to_the_max() {
+    let reconnect = Reconnect {
+        initial_delay: 500,
+        max_delay: 3_000,
+        max_attempts: None,
+    };
+    let delays: Vec<_> =
+        (1..=5).map(|attempt| reconnect.delay(attempt)).collect();
+    assert_eq!(
+        delays,
+        vec![
+            Some(500),
+            Some(1_000),
+            Some(2_000),
+            Some(3_000),
+            Some(3_000)
+        ]
+    );
+    assert_eq!(reconnect.delay(100), Some(3_000));
+}
+
+#[test]
+fn reconnect_stops_after_max_attempts() {
+    let reconnect = Reconnect {
+        max_attempts: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(reconnect.delay(2), Some(1_000));
+    assert_eq!(reconnect.delay(3), None);
+}
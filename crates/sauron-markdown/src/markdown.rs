@@ -42,14 +42,11 @@ pub fn render_markdown<'a, MSG>(src: &'a str) -> Vec<Node<MSG>> {
             Event::Start(tag) => {
                 spine.push(make_tag(tag, &mut numbers));
             }
-            Event::Text(content) => {
-                let content = ammonia::clean(&*content);
-                add_child!(text(content));
-            }
+            // the text is escaped when rendered, so it is not cleaned here
+            Event::Text(content) => add_child!(text(content)),
             Event::SoftBreak => add_child!(text("\n")),
             Event::HardBreak => add_child!(br(vec![], vec![])),
             Event::Code(code_str) => {
-                let code_str = ammonia::clean(&*code_str);
                 add_child!(code(vec![], vec![text(code_str)]))
             }
             // Warning: this use the `sauron-parse`
@@ -280,7 +277,7 @@ Duplicated footnote reference[^second].
 <img src="img.jpeg"/>"#;
 
         let expected =
-            "<p>\n    <a href=\"link.html\" title=\"\">Hello</a>\n    \n\n    <img src=\"img.jpeg\"></img>\n</p>";
+            "<p>\n    <a href=\"link.html\" title=\"\">Hello</a>\n    \n\n    <img src=\"img.jpeg\">\n</p>";
         let view: Node<()> = markdown(md);
 
         let mut buffer = String::new();
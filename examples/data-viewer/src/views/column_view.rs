@@ -1,12 +1,13 @@
 use crate::{assets, widgets::search_widget, ColumnDef};
 use sauron::{
     html::{attributes::*, events::*, units::*, *},
-    Cmd, Component, Node,
+    Cmd, Component, Node, Timer, TimerHandle,
 };
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Msg {
     ChangeSearch(String),
+    Search(String),
 }
 
 pub struct ColumnView {
@@ -14,6 +15,8 @@ pub struct ColumnView {
     pub width: i32,
     pub height: i32,
     pub is_frozen: bool,
+    /// debounce the search while the user is typing
+    search_timer: TimerHandle,
 }
 
 impl ColumnView {
@@ -23,6 +26,7 @@ impl ColumnView {
             width: 220,
             height: 70,
             is_frozen: false,
+            search_timer: TimerHandle::new(),
         }
     }
 
@@ -114,6 +118,9 @@ impl Component<Msg> for ColumnView {
     fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
         match msg {
             Msg::ChangeSearch(search) => {
+                Timer::debounce(&self.search_timer, 300, Msg::Search(search))
+            }
+            Msg::Search(search) => {
                 trace!("Search term change: {}", search);
                 Cmd::none()
             }
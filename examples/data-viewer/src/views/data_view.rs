@@ -465,13 +465,13 @@ impl DataView {
 impl Component<Msg> for DataView {
     fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
         match msg {
-            Msg::PageMsg(page_index, page_msg) => {
-                self.page_views[page_index].update(page_msg);
-                Cmd::none()
-            }
+            Msg::PageMsg(page_index, page_msg) => self.page_views[page_index]
+                .update(page_msg)
+                .map_msg(move |page_msg| Msg::PageMsg(page_index, page_msg)),
             Msg::ColumnMsg(column_index, column_msg) => {
-                self.column_views[column_index].update(column_msg);
-                Cmd::none()
+                self.column_views[column_index].update(column_msg).map_msg(
+                    move |column_msg| Msg::ColumnMsg(column_index, column_msg),
+                )
             }
             Msg::Scrolled((scroll_top, scroll_left)) => {
                 self.scroll_top = scroll_top;
@@ -242,10 +242,9 @@ impl PageView {
 impl Component<Msg> for PageView {
     fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
         match msg {
-            Msg::RowMsg(row_index, row_msg) => {
-                self.row_views[row_index].update(row_msg);
-                Cmd::none()
-            }
+            Msg::RowMsg(row_index, row_msg) => self.row_views[row_index]
+                .update(row_msg)
+                .map_msg(move |row_msg| Msg::RowMsg(row_index, row_msg)),
         }
     }
 
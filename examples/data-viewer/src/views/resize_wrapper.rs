@@ -66,10 +66,10 @@ impl Component<Msg> for ResizeWrapper {
 
     fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
         match msg {
-            Msg::DataViewMsg(data_view_msg) => {
-                self.data_view.update(data_view_msg);
-                Cmd::none()
-            }
+            Msg::DataViewMsg(data_view_msg) => self
+                .data_view
+                .update(data_view_msg)
+                .map_msg(Msg::DataViewMsg),
             Msg::EndResize(_client_x, _client_y) => {
                 self.active_resize = None;
                 Cmd::none()
@@ -148,10 +148,12 @@ impl RowView {
 impl Component<Msg> for RowView {
     fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
         match msg {
-            Msg::FieldMsg(field_index, field_msg) => {
-                self.fields[field_index].borrow_mut().update(field_msg);
-                Cmd::none()
-            }
+            Msg::FieldMsg(field_index, field_msg) => self.fields[field_index]
+                .borrow_mut()
+                .update(field_msg)
+                .map_msg(move |field_msg| {
+                    Msg::FieldMsg(field_index, field_msg)
+                }),
             Msg::DoubleClick => Cmd::none(),
             Msg::Click => Cmd::none(),
         }
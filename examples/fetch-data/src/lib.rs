@@ -1,5 +1,5 @@
 #![deny(warnings)]
-use sauron::prelude::*;
+use sauron::{dom::http::HttpError, prelude::*};
 use serde::Deserialize;
 
 #[macro_use]
@@ -11,7 +11,7 @@ const DATA_URL: &'static str = "https://reqres.in/api/users";
 pub enum Msg {
     NextPage,
     PrevPage,
-    ReceivedData(Result<Data, JsValue>),
+    ReceivedData(Result<Data, HttpError>),
 }
 
 pub struct App {
@@ -49,15 +49,7 @@ impl App {
 
     fn fetch_page(&self) -> Cmd<Self, Msg> {
         let url = format!("{}?page={}", DATA_URL, self.page);
-        Http::fetch_with_text_response_decoder(
-            &url,
-            |v: String| {
-                let data: Result<Data, _> = serde_json::from_str(&v);
-                trace!("data: {:#?}", data);
-                data.expect("Error deserializing data")
-            },
-            Msg::ReceivedData,
-        )
+        Http::get(&url).fetch_json(Msg::ReceivedData)
     }
 }
 
@@ -161,11 +153,11 @@ impl Component<Msg> for App {
                 self.data = data;
                 Cmd::none()
             }
-            Msg::ReceivedData(Err(js_value)) => {
-                trace!("Error fetching users! {:#?}", js_value);
+            Msg::ReceivedData(Err(error)) => {
+                trace!("Error fetching users! {:#?}", error);
                 self.error = Some(format!(
-                    "There was an error fetching the page: {:?}",
-                    js_value
+                    "There was an error fetching the page: {}",
+                    error
                 ));
                 Cmd::none()
             }
@@ -1,9 +1,14 @@
 #![deny(warnings)]
 use chrono::Local;
+use futures::{executor::block_on, FutureExt, StreamExt};
 use sauron::html::{attributes::*, *};
 use sauron::*;
 use serde_derive::{Deserialize, Serialize};
-use warp::{http::Response, Filter};
+use warp::{
+    http::{header::CONTENT_TYPE, Response},
+    hyper::Body,
+    Filter,
+};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct FormData {
@@ -101,6 +106,25 @@ fn view(form_data: Option<FormData>) -> Node<()> {
     )
 }
 
+/// render the view in the blocking thread pool and send the html in chunks while it is rendered,
+/// the view is created in that thread since the nodes can not be sent across threads
+fn stream_view(form_data: Option<FormData>) -> Response<Body> {
+    let (mut sender, body) = Body::channel();
+    tokio::task::spawn_blocking(move || {
+        let node = view(form_data);
+        for chunk in RenderChunks::new(&node, RenderOptions::default(), 8192) {
+            if block_on(sender.send_data(chunk.into())).is_err() {
+                // the client has disconnected
+                break;
+            }
+        }
+    });
+    Response::builder()
+        .header(CONTENT_TYPE, "text/html; charset=utf-8")
+        .body(body)
+        .expect("must build the response")
+}
+
 #[tokio::main]
 async fn main() {
     let submission = warp::get()
@@ -108,20 +132,24 @@ async fn main() {
         .and(warp::query::<FormData>())
         .map(|form_data: FormData| {
             println!("form data: {:#?}", form_data);
-            let mut buffer = String::new();
-            let node = view(Some(form_data));
-            node.render(&mut buffer).expect("must render");
-            Response::builder().body(buffer)
+            stream_view(Some(form_data))
         });
 
-    let index = warp::get().map(|| {
-        let mut buffer = String::new();
-        let node = view(None);
-        node.render(&mut buffer).expect("must render");
-        Response::builder().body(buffer)
+    let index = warp::get().map(|| stream_view(None));
+
+    // sends back the frames it receives, used for testing the WebSocket client
+    let echo = warp::path("echo").and(warp::ws()).map(|ws: warp::ws::Ws| {
+        ws.on_upgrade(|websocket| {
+            let (tx, rx) = websocket.split();
+            rx.forward(tx).map(|result| {
+                if let Err(e) = result {
+                    println!("websocket error: {:?}", e);
+                }
+            })
+        })
     });
 
-    warp::serve(submission.or(index))
+    warp::serve(echo.or(submission).or(index))
         .run(([127, 0, 0, 1], 3030))
         .await;
 }
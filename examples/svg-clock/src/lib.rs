@@ -7,7 +7,6 @@ use console_error_panic_hook;
 use js_sys::Date;
 use sauron::html::attributes::style;
 use sauron::prelude::*;
-use sauron::wasm_bindgen::JsCast;
 
 #[macro_use]
 extern crate log;
@@ -18,34 +17,24 @@ pub enum Msg {
 
 pub struct Clock {
     date: Date,
+    // the interval is stopped when this is dropped
+    ticker: TimerHandle,
 }
 
 impl Clock {
     pub fn new() -> Self {
         Clock {
             date: Date::new_0(),
+            ticker: TimerHandle::new(),
         }
     }
 }
 
 impl Component<Msg> for Clock {
-    // we wire the window set_interval api to trigger an Msg::Tick
-    // by dispatching it from the program, through the Cmd interface
+    // we start an interval which triggers an Msg::Tick
+    // every 30ms, through the Cmd interface
     fn init(&self) -> Cmd<Self, Msg> {
-        Cmd::new(move |program| {
-            let clock: Closure<dyn Fn()> = Closure::wrap(Box::new(move || {
-                program.dispatch(Msg::Tick);
-            }));
-
-            web_sys::window()
-                .expect("no global `window` exists")
-                .set_interval_with_callback_and_timeout_and_arguments_0(
-                    clock.as_ref().unchecked_ref(),
-                    30,
-                )
-                .expect("Unable to start interval");
-            clock.forget();
-        })
+        Timer::interval(&self.ticker, 30, || Msg::Tick)
     }
 
     fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
@@ -103,7 +103,6 @@ impl Component<Msg> for Model {
             }
             Msg::NoOp => {}
         }
-        self.save_to_storage();
         Cmd::none()
     }
 
@@ -362,33 +361,11 @@ impl Model {
         )
     }
 
-    fn save_to_storage(&self) {
-        let window = web_sys::window().expect("no global `window` exists");
-        let local_storage = window.local_storage();
-        if let Ok(Some(local_storage)) = local_storage {
-            let json_data =
-                serde_json::to_string(&self).expect("must serialize data");
-            if let Err(err) =
-                local_storage.set_item("todomvc::data", &json_data)
-            {
-                log::error!("Could not write to local storage, {:?}", err);
-            }
-        }
-    }
-
     pub fn get_from_storage() -> Self {
-        let window = web_sys::window().expect("no global `window` exists");
-        let local_storage = window.local_storage();
-
-        if let Ok(Some(local_storage)) = local_storage {
-            if let Ok(Some(s)) = local_storage.get_item("todomvc::data") {
-                serde_json::from_str(&s).ok().unwrap_or(Self::new())
-            } else {
-                Self::new()
-            }
-        } else {
-            Self::new()
-        }
+        Storage::get(StorageArea::Local, "todomvc::data")
+            .ok()
+            .flatten()
+            .unwrap_or_else(Self::new)
     }
 }
 
@@ -1,6 +1,6 @@
 #![deny(warnings)]
 use app::Model;
-use sauron::Program;
+use sauron::{dom::StorageArea, Program};
 use wasm_bindgen::prelude::*;
 
 #[macro_use]
@@ -20,5 +20,6 @@ pub fn main() {
         console_error_panic_hook::set_once();
     }
     trace!("in main!");
-    Program::mount_to_body(Model::get_from_storage());
+    let program = Program::mount_to_body(Model::get_from_storage());
+    program.persist_model(StorageArea::Local, "todomvc::data");
 }
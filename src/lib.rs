@@ -174,7 +174,9 @@ cfg_if! {if #[cfg(feature = "with-dom")] {
     pub use sauron_core::dom::*;
     pub use sauron_core::web_sys;
     pub use sauron_core::wasm_bindgen;
-    pub use sauron_core::{Component, Cmd, Program};
+    pub use sauron_core::Program;
+} else {
+    pub use sauron_core::html::events::{self, *};
 }}
 
 /// reexport prelude from sauron core
@@ -184,7 +186,10 @@ pub mod prelude {
     pub use sauron_node_macro::node;
 }
 pub use sauron_core::{
-    diff, html, jss, Attribute, Callback, Element, Node, Patch, Render,
+    assert_snapshot, cmd, diff, html, jss, router, snapshot, ssr, sub,
+    Attribute, Callback, Cmd, Component, Dispatch, Element, HtmlWriter, Node,
+    Patch, QuerySelector, Render, RenderChunks, RenderOptions, Selector,
+    SelectorError, ServerProgram, Sub,
 };
 
 #[cfg(any(feature = "with-markdown", feature = "with-lite-markdown"))]